@@ -11,13 +11,13 @@ pub struct FilePos {
 }
 
 impl FilePos {
-    /// Gets a new [FilePos] from given input, the ind to find and the filepath
-    /// to display. If this returns [None], the file ended before expected
+    /// Gets a new [FilePos] from given input, the byte ind to find and the
+    /// filepath to display. If this returns [None], the file ended before expected
     pub fn new(path: impl Into<Option<PathBuf>>, input: &str, ind: usize) -> Option<Self> {
         let mut line: usize = 1;
         let mut col: usize = 1;
 
-        for (input_ind, c) in input.chars().enumerate() {
+        for (input_ind, c) in input.char_indices() {
             if input_ind == ind {
                 return Some(Self {
                     path: path.into(),
@@ -34,7 +34,7 @@ pub struct Parsed {
 impl Parsed {
     /// Creates new [Parsed] using default [env::args]
     fn new() -> Self {
-        let mut env_args = env::args().into_iter();
+        let mut env_args = env::args();
         env_args.next(); // skip over first
         Parsed::custom(env_args.collect())
     }
@@ -42,7 +42,7 @@ impl Parsed {
     /// Parses custom arguments
     fn custom(args: Vec<String>) -> Self {
         // TODO: use [OsString]
-        if args.len() == 0 {
+        if args.is_empty() {
             utils::help_exit("No arguments passed");
         }
 
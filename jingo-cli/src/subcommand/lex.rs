@@ -3,13 +3,12 @@
 use crate::utils::{help_exit, msg_exit, open_file};
 use crate::{FilePos, Parsed};
 
-use jingo_lib::frontend::lexer::Token;
-use logos::Logos;
+use jingo_lib::frontend::lexer;
 use std::path::PathBuf;
 
 /// Runs lexing steps
 pub fn launch(parsed: Parsed) {
-    if parsed.data.len() == 0 {
+    if parsed.data.is_empty() {
         help_exit("No files passed for lexing")
     } else if parsed.data.len() > 1 {
         help_exit("More then one file passed for lexing")
@@ -18,20 +17,22 @@ pub fn launch(parsed: Parsed) {
     let path = PathBuf::from(parsed.data[0].clone());
     let input = &open_file(path.clone());
 
-    let mut lex = Token::lexer(input);
-    let mut output = vec![];
-
-    loop {
-        // seperate loop in order to print all at once for error consistancy
-        match lex.next() {
-            Some(Token::Error) => msg_exit(format!(
-                "Error in {}\n  Unknown token whilst lexing: '{}'",
-                FilePos::new(path, input, lex.span().start).unwrap(),
-                lex.slice()
-            )),
-            Some(token) => output.push((token, lex.span())),
-            None => break,
-        }
+    let (output, diagnostics) = lexer::launch(input);
+
+    if !diagnostics.is_empty() {
+        // all errors are shown at once so they may be fixed in one go
+        msg_exit(
+            diagnostics
+                .into_iter()
+                .map(
+                    |diag| match FilePos::new(path.clone(), input, diag.span.start) {
+                        Some(pos) => format!("Error in {}\n  Whilst lexing: {}", pos, diag),
+                        None => format!("Error at end of {:?}\n  Whilst lexing: {}", path, diag),
+                    },
+                )
+                .collect::<Vec<String>>()
+                .join("\n\n"),
+        )
     }
 
     println!("Lexed tokens:");
@@ -2,13 +2,17 @@
 
 use crate::utils::{help_exit, msg_exit, open_file};
 use crate::{FilePos, Parsed};
-use jingo_lib::frontend::{lexer::Token, parser};
+use jingo_lib::frontend::{
+    check, doc,
+    lexer::{self, Token},
+    parser,
+};
 use logos::Logos;
 use std::path::PathBuf;
 
 /// Runs parsing steps
 pub fn launch(parsed: Parsed) {
-    if parsed.data.len() == 0 {
+    if parsed.data.is_empty() {
         help_exit("No files passed for parsing")
     } else if parsed.data.len() > 1 {
         help_exit("More then one file passed for parsing")
@@ -18,13 +22,60 @@ pub fn launch(parsed: Parsed) {
     let input = &open_file(path.clone());
 
     let mut lex = Token::lexer(input);
+    let (mut output, errors) = parser::launch_recover(&mut lex);
 
-    match parser::launch(&mut lex) {
-        Ok(parsed) => println!("Parsed expressions:\n{:#?}", parsed),
-        Err(err) => msg_exit(format!(
-            "Error in {}\n  Whilst parsing: {}",
-            FilePos::new(path, input, lex.span().start).unwrap(),
-            err
-        )),
+    if !errors.is_empty() {
+        // all errors are shown at once so they may be fixed in one go
+        msg_exit(
+            errors
+                .into_iter()
+                .map(|err| {
+                    match err
+                        .span()
+                        .and_then(|span| FilePos::new(path.clone(), input, span.start))
+                    {
+                        Some(pos) => format!("Error in {}\n  Whilst parsing: {}", pos, err),
+                        None => format!("Error at end of {:?}\n  Whilst parsing: {}", path, err),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n\n"),
+        )
     }
+
+    let errors = check::launch(&output);
+
+    if !errors.is_empty() {
+        msg_exit(
+            errors
+                .into_iter()
+                .map(
+                    |err| match FilePos::new(path.clone(), input, err.range().start) {
+                        Some(pos) => format!("Error in {}\n  Whilst checking: {}", pos, err),
+                        None => format!("Error at end of {:?}\n  Whilst checking: {}", path, err),
+                    },
+                )
+                .collect::<Vec<String>>()
+                .join("\n\n"),
+        )
+    }
+
+    // lints only warn so they don't stop parsing output
+    let lints = lexer::mixed_scripts(input)
+        .into_iter()
+        .map(|lint| (lint.span.start, lint.to_string()))
+        .chain(
+            doc::launch(&mut output)
+                .into_iter()
+                .map(|warning| (warning.range().start, warning.to_string())),
+        );
+
+    for (start, lint) in lints {
+        match FilePos::new(path.clone(), input, start) {
+            Some(pos) => eprintln!("Warning in {}\n  Whilst linting: {}\n", pos, lint),
+            None => eprintln!("Warning at end of {:?}\n  Whilst linting: {}\n", path, lint),
+        }
+    }
+
+    println!("Parsed expressions:\n{:#?}", output)
 }
@@ -0,0 +1,68 @@
+//! Lexing throughput benchmark over a large generated source, comparing the
+//! borrowing [Token]s against converting each of them into an owned token as
+//! the lexer used to, alongside parsing the same source so that slowdowns in
+//! how the parser drives the lexer show up too. Run with
+//! `cargo bench -p jingo-lib`
+
+use jingo_lib::frontend::{lexer::Token, parser};
+use logos::Logos;
+use std::time::{Duration, Instant};
+
+/// Chunk of typical source repeated to generate the benchmarked input
+const CHUNK: &str = r#"--- Creates new [SomeClass] from `x` value
+--- @param x Value to store
+fun SomeClass::new(x) {
+    self.x = x;
+    let name = "some string value";
+    let other = my_module::inner::value + 1_000 * 2.5;
+    if name == "other" and other >= 10 { return none; }
+}
+"#;
+
+/// Number of times [CHUNK] is repeated, giving roughly 25MB of source
+const REPEATS: usize = 100_000;
+
+/// Number of runs for each case, of which the fastest is reported
+const RUNS: usize = 10;
+
+fn main() {
+    let source = CHUNK.repeat(REPEATS);
+
+    let borrowed = fastest(|| Token::lexer(&source).count());
+    let owned = fastest(|| Token::lexer(&source).map(Token::into_owned).count());
+    let parsed = fastest(|| parser::launch(&mut Token::lexer(&source)).unwrap().len());
+
+    report("borrowed", &source, borrowed);
+    report("owned", &source, owned);
+    report("parsed", &source, parsed);
+    println!(
+        "borrowed tokens are {:.2}x the throughput of owned tokens",
+        owned.as_secs_f64() / borrowed.as_secs_f64()
+    );
+    println!(
+        "parsing takes {:.2}x as long as lexing with borrowed tokens",
+        parsed.as_secs_f64() / borrowed.as_secs_f64()
+    );
+}
+
+/// Gets the fastest time taken to run `case` out of [RUNS] runs
+fn fastest(mut case: impl FnMut() -> usize) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            assert!(case() > 0);
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Shows the throughput of handling the whole `source` in the `time` given
+fn report(name: &str, source: &str, time: Duration) {
+    println!(
+        "{:>8}: {:>7.1} MB/s ({:?})",
+        name,
+        source.len() as f64 / time.as_secs_f64() / 1_000_000.0,
+        time
+    );
+}
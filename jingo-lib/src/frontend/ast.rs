@@ -1,5 +1,8 @@
 //! Expression-centric abstract syntax tree for Jingo
 
+use super::doc::Doc;
+use std::ops::Range;
+
 /// Central expression structure, defining the fundamental structure of Jingo
 ///
 /// To parse into this structure and therefore an [ExprKind], please use the
@@ -10,20 +13,24 @@ pub struct Expr {
     /// for an expression
     pub kind: ExprKind,
 
-    /// Optional documentation string
-    pub doc: Option<String>,
+    /// Optional structured documentation
+    pub doc: Option<Doc>,
 
-    /// Starting index of this expression
-    pub start: usize,
+    /// Byte range of this expression, covering the whole construct
+    pub range: Range<usize>,
 }
 
 impl Expr {
     /// Shortcut method for getting from parsing
-    pub(crate) fn from_parse(kind: impl Into<ExprKind>, doc: Option<String>, start: usize) -> Self {
+    pub(crate) fn from_parse(
+        kind: impl Into<ExprKind>,
+        doc: Option<Doc>,
+        range: Range<usize>,
+    ) -> Self {
         Self {
             kind: kind.into(),
             doc,
-            start,
+            range,
         }
     }
 }
@@ -33,23 +40,34 @@ impl Expr {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExprKind {
     Not(Not),
+    Neg(Neg),
+    BitNot(BitNot),
     Op(Op),
+    Block(Block),
     Path(Path),
     Class(Class),
     Function(Function),
     Method(Method),
     FunctionCall(FunctionCall),
     MethodCall(MethodCall),
+    Field(Field),
+    This(This),
     If(If),
     While(While),
+    Loop(Loop),
+    Break(Break),
+    Continue(Continue),
     Return(Return),
     Let(Let),
     SetLet(SetLet),
+    OpSetLet(OpSetLet),
     IntLit(IntLit),
     FloatLit(FloatLit),
     StrLit(StrLit),
+    InterpStr(InterpStr),
     CharLit(CharLit),
     BoolLit(BoolLit),
+    NoneLit(NoneLit),
 }
 
 /// Right-associative not symbol
@@ -62,6 +80,26 @@ impl From<Not> for ExprKind {
     }
 }
 
+/// Right-associative negation symbol, i.e. `-5`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neg(pub Box<Expr>);
+
+impl From<Neg> for ExprKind {
+    fn from(kind: Neg) -> ExprKind {
+        ExprKind::Neg(kind)
+    }
+}
+
+/// Right-associative bitwise not symbol, i.e. `~5`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitNot(pub Box<Expr>);
+
+impl From<BitNot> for ExprKind {
+    fn from(kind: BitNot) -> ExprKind {
+        ExprKind::BitNot(kind)
+    }
+}
+
 /// Binary operation allowing two [Expr]s to be modified by a mathematical notation
 #[derive(Debug, Clone, PartialEq)]
 pub struct Op {
@@ -88,6 +126,13 @@ pub enum OpKind {
     Sub,
     Mul,
     Div,
+    Mod,
+    Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
     Greater,
     GreaterEq,
     Less,
@@ -98,18 +143,31 @@ pub enum OpKind {
     Or,
     PlusEq,
     SubEq,
+    MulEq,
+    DivEq,
 }
 
-/// Pre-validated valid identifier
+/// Block of expressions enclosed in `{}`, the value of which is the value of
+/// its last expression
 #[derive(Debug, Clone, PartialEq)]
-pub struct Id(pub String);
+pub struct Block(pub Vec<Expr>);
 
-impl From<String> for Id {
-    fn from(string: String) -> Self {
-        Id(string)
+impl From<Block> for ExprKind {
+    fn from(kind: Block) -> ExprKind {
+        ExprKind::Block(kind)
     }
 }
 
+/// Pre-validated valid identifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct Id {
+    /// Name of the identifier
+    pub name: String,
+
+    /// Byte range of this identifier
+    pub range: Range<usize>,
+}
+
 /// Path to something, `::` seperated
 ///
 /// # Internals
@@ -126,10 +184,7 @@ impl Path {
 
     pub fn last_2(&mut self) -> Option<(Id, Id)> {
         match self.0.pop() {
-            Some(first) => match self.0.pop() {
-                Some(second) => Some((first, second)),
-                None => None,
-            },
+            Some(first) => self.0.pop().map(|second| (first, second)),
             None => None,
         }
     }
@@ -145,9 +200,17 @@ impl From<Path> for ExprKind {
     }
 }
 
-/// Class definition
+/// Class definition, optionally declaring the fields objects of this class
+/// are expected to hold
 #[derive(Debug, Clone, PartialEq)]
-pub struct Class(pub Id);
+pub struct Class {
+    /// Identifier of the class
+    pub id: Id,
+
+    /// Fields declared in the class body, this is empty for a bodiless
+    /// `class Name;` definition
+    pub fields: Vec<ClassField>,
+}
 
 impl From<Class> for ExprKind {
     fn from(kind: Class) -> ExprKind {
@@ -155,6 +218,19 @@ impl From<Class> for ExprKind {
     }
 }
 
+/// Field declared within a [Class] body
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassField {
+    /// Identifier of the field
+    pub id: Id,
+
+    /// Optional default value of the field
+    pub default: Option<Expr>,
+
+    /// Optional structured documentation
+    pub doc: Option<Doc>,
+}
+
 /// Subprogram allowing code modularity, recurses down into more [Expr]
 /// nodes. This is different from the [Method] structure as this one is for
 /// non-class-linked subprograms
@@ -226,16 +302,13 @@ impl From<FunctionCall> for ExprKind {
 /// Caller for a method, allows invoking methods with passed arguments
 #[derive(Debug, Clone, PartialEq)]
 pub struct MethodCall {
-    /// Reference to the class name (which should be an existing [Class]) the
-    /// method is linked to
-    pub class_id: Id,
+    /// Object the method is called on, which may be any expression such as the
+    /// `SomeClass::new(3)` of `SomeClass::new(3).multiply(2)`
+    pub receiver: Box<Expr>,
 
-    /// Identifier of the function ([Id::range.start] should be used as the start)
+    /// Identifier of the method
     pub id: Id,
 
-    /// Path before [MethodCall::class_id] and [MethodCall::Id] for scoping
-    pub path: Path,
-
     /// Argument to pass and invoke within the function
     pub args: Vec<Expr>,
 }
@@ -246,6 +319,34 @@ impl From<MethodCall> for ExprKind {
     }
 }
 
+/// Getter for a field stored on an object, e.g. `self.x`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// Object owning the field, which may be any expression such as the `a.b`
+    /// of `a.b.c` or the `foo()` of `foo().bar`
+    pub receiver: Box<Expr>,
+
+    /// Identifier of the field
+    pub id: Id,
+}
+
+impl From<Field> for ExprKind {
+    fn from(kind: Field) -> ExprKind {
+        ExprKind::Field(kind)
+    }
+}
+
+/// Reference to the object a [Method] was called on, written as `self`. Field
+/// accesses and method calls such as `self.x` use this as their receiver
+#[derive(Debug, Clone, PartialEq)]
+pub struct This;
+
+impl From<This> for ExprKind {
+    fn from(kind: This) -> ExprKind {
+        ExprKind::This(kind)
+    }
+}
+
 /// Basic single-argument matching as part of a broader [If]
 #[derive(Debug, Clone, PartialEq)]
 pub struct IfSegment {
@@ -258,7 +359,7 @@ pub struct IfSegment {
 
 /// Default value for [If] statement, typically known as `else`
 #[derive(Debug, Clone, PartialEq)]
-pub struct IfDefault(Vec<Expr>);
+pub struct IfDefault(pub Vec<Expr>);
 
 /// Broader structure for basic single-argument matching
 #[derive(Debug, Clone, PartialEq)]
@@ -292,6 +393,41 @@ impl From<While> for ExprKind {
     }
 }
 
+/// Infinite loop, firing the body repeatedly until a [Break] is reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loop {
+    /// Body of loop
+    pub body: Vec<Expr>,
+}
+
+impl From<Loop> for ExprKind {
+    fn from(kind: Loop) -> ExprKind {
+        ExprKind::Loop(kind)
+    }
+}
+
+/// Break expression allowing early exit from a [While] or [Loop], optionally
+/// passing back a value as loops are expressions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Break(pub Option<Box<Expr>>);
+
+impl From<Break> for ExprKind {
+    fn from(kind: Break) -> ExprKind {
+        ExprKind::Break(kind)
+    }
+}
+
+/// Continue expression, skipping the rest of the body for the current
+/// iteration of a [While] or [Loop]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Continue;
+
+impl From<Continue> for ExprKind {
+    fn from(kind: Continue) -> ExprKind {
+        ExprKind::Continue(kind)
+    }
+}
+
 /// Return expression allowing pass-back from functions
 #[derive(Debug, Clone, PartialEq)]
 pub struct Return(pub Box<Expr>);
@@ -327,10 +463,14 @@ impl From<Let> for ExprKind {
 /// [Let::mutable] is [true]
 #[derive(Debug, Clone, PartialEq)]
 pub struct SetLet {
-    /// Let identifier ([Id::range.start] should be used as the start)
+    /// Object owning the field being set, such as the `self` of `self.x = 1`,
+    /// or [None] when setting a let
+    pub receiver: Option<Box<Expr>>,
+
+    /// Let or field identifier ([Id::range.start] should be used as the start)
     pub id: Id,
 
-    /// Path to identifier
+    /// Path to identifier, which is empty when setting a field
     pub path: Path,
 
     /// Expression determining what [SetLet::id] should be set to
@@ -343,6 +483,34 @@ impl From<SetLet> for ExprKind {
     }
 }
 
+/// Compound let setter such as `x += 1`, overwriting data in an existing [Let]
+/// with the result of [OpSetLet::kind] applied to its current value and
+/// [OpSetLet::expr] whilst [Let::mutable] is [true]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpSetLet {
+    /// Object owning the field being set, see [SetLet::receiver]
+    pub receiver: Option<Box<Expr>>,
+
+    /// Let or field identifier ([Id::range.start] should be used as the start)
+    pub id: Id,
+
+    /// Path to identifier, which is empty when setting a field
+    pub path: Path,
+
+    /// Compound operation to apply, being one of [OpKind::PlusEq],
+    /// [OpKind::SubEq], [OpKind::MulEq] or [OpKind::DivEq]
+    pub kind: OpKind,
+
+    /// Expression used as the righthand side of [OpSetLet::kind]
+    pub expr: Box<Expr>,
+}
+
+impl From<OpSetLet> for ExprKind {
+    fn from(kind: OpSetLet) -> ExprKind {
+        ExprKind::OpSetLet(kind)
+    }
+}
+
 /// Integer literal used for defining raw integers
 #[derive(Debug, Clone, PartialEq)]
 pub struct IntLit(pub i64);
@@ -373,6 +541,25 @@ impl From<StrLit> for ExprKind {
     }
 }
 
+/// Interpolated string such as `"hello {name}"`, made up of literal text and
+/// embedded expressions in the order they were written
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterpStr(pub Vec<InterpPart>);
+
+impl From<InterpStr> for ExprKind {
+    fn from(kind: InterpStr) -> ExprKind {
+        ExprKind::InterpStr(kind)
+    }
+}
+
+/// Part of an [InterpStr], being either literal text or an expression embedded
+/// between `{` and `}`
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpPart {
+    Lit(String),
+    Expr(Box<Expr>),
+}
+
 /// Char literal used for defining raw chars
 #[derive(Debug, Clone, PartialEq)]
 pub struct CharLit(pub char);
@@ -392,3 +579,13 @@ impl From<BoolLit> for ExprKind {
         ExprKind::BoolLit(kind)
     }
 }
+
+/// None literal used for representing the absence of a value
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoneLit;
+
+impl From<NoneLit> for ExprKind {
+    fn from(kind: NoneLit) -> ExprKind {
+        ExprKind::NoneLit(kind)
+    }
+}
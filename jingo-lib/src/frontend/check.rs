@@ -0,0 +1,246 @@
+//! Checking stage ran after parsing, validating the produced [Expr]s for
+//! mistakes which the grammar alone can't catch such as assigning to
+//! immutable lets
+
+use super::ast::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// Error enumeration representing errors found whilst checking parsed
+/// expressions, each containing the byte range it occurred at
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckError {
+    /// Let was assigned to without being declared beforehand
+    Undeclared { range: Range<usize>, id: String },
+
+    /// Let was assigned to but wasn't declared with `let mut`
+    Immutable { range: Range<usize>, id: String },
+}
+
+impl CheckError {
+    /// Gets the byte range this error occurred at
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            CheckError::Undeclared { range, .. } | CheckError::Immutable { range, .. } => {
+                range.clone()
+            }
+        }
+    }
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::Undeclared { id, .. } => {
+                write!(f, "Cannot assign to `{}` as it hasn't been declared", id)
+            }
+            CheckError::Immutable { id, .. } => write!(
+                f,
+                "Cannot assign to `{}` as it wasn't declared with `let mut`",
+                id
+            ),
+        }
+    }
+}
+
+/// Checks all given expressions, returning every error found
+pub fn launch(exprs: &[Expr]) -> Vec<CheckError> {
+    let mut checker = Checker::default();
+    checker.scoped(exprs, &[]);
+    checker.errors
+}
+
+/// Walker over expressions, keeping track of the mutability of the lets in
+/// each nested scope
+#[derive(Default)]
+struct Checker {
+    scopes: Vec<HashMap<String, bool>>,
+    errors: Vec<CheckError>,
+}
+
+impl Checker {
+    /// Checks a list of expressions inside of a new scope, declaring the
+    /// immutable `args` of a function into it beforehand
+    fn scoped(&mut self, exprs: &[Expr], args: &[Id]) {
+        self.scopes
+            .push(args.iter().map(|arg| (arg.name.clone(), false)).collect());
+
+        for expr in exprs {
+            self.expr(expr)
+        }
+
+        self.scopes.pop();
+    }
+
+    /// Checks a single expression and any of its children
+    fn expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Not(Not(inner))
+            | ExprKind::Neg(Neg(inner))
+            | ExprKind::BitNot(BitNot(inner)) => self.expr(inner),
+            ExprKind::Op(op) => {
+                self.expr(&op.left);
+                self.expr(&op.right)
+            }
+            ExprKind::Block(Block(body)) | ExprKind::Loop(Loop { body }) => self.scoped(body, &[]),
+            ExprKind::Class(class) => class
+                .fields
+                .iter()
+                .filter_map(|field| field.default.as_ref())
+                .for_each(|default| self.expr(default)),
+            ExprKind::Function(Function { args, body, .. })
+            | ExprKind::Method(Method { args, body, .. }) => self.scoped(body, args),
+            ExprKind::FunctionCall(FunctionCall { args, .. }) => {
+                args.iter().for_each(|arg| self.expr(arg))
+            }
+            ExprKind::MethodCall(MethodCall { receiver, args, .. }) => {
+                self.expr(receiver);
+                args.iter().for_each(|arg| self.expr(arg))
+            }
+            ExprKind::Field(Field { receiver, .. }) => self.expr(receiver),
+            ExprKind::If(If { segments, default }) => {
+                for segment in segments {
+                    self.expr(&segment.condition);
+                    self.scoped(&segment.body, &[]);
+                }
+
+                if let Some(IfDefault(body)) = default {
+                    self.scoped(body, &[])
+                }
+            }
+            ExprKind::While(While { condition, body }) => {
+                self.expr(condition);
+                self.scoped(body, &[])
+            }
+            ExprKind::InterpStr(InterpStr(parts)) => {
+                for part in parts {
+                    if let InterpPart::Expr(inner) = part {
+                        self.expr(inner)
+                    }
+                }
+            }
+            ExprKind::Break(Break(Some(inner))) | ExprKind::Return(Return(inner)) => {
+                self.expr(inner)
+            }
+            ExprKind::Let(Let { mutable, id, expr }) => {
+                self.expr(expr);
+                self.scopes
+                    .last_mut()
+                    .unwrap()
+                    .insert(id.name.clone(), *mutable);
+            }
+            ExprKind::SetLet(SetLet {
+                receiver,
+                id,
+                path,
+                expr,
+            })
+            | ExprKind::OpSetLet(OpSetLet {
+                receiver,
+                id,
+                path,
+                expr,
+                ..
+            }) => {
+                self.expr(expr);
+
+                match receiver {
+                    Some(receiver) => self.expr(receiver),
+                    None => self.assign(id, path),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Ensures the let being assigned to is declared and mutable, skipping
+    /// lets in other scopes as their mutability is only known at runtime.
+    /// Fields are never checked for the same reason
+    fn assign(&mut self, id: &Id, path: &Path) {
+        if !path.0.is_empty() {
+            return;
+        }
+
+        match self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&id.name))
+        {
+            Some(true) => (),
+            Some(false) => self.errors.push(CheckError::Immutable {
+                range: id.range.clone(),
+                id: id.name.clone(),
+            }),
+            None => self.errors.push(CheckError::Undeclared {
+                range: id.range.clone(),
+                id: id.name.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::{lexer::Token, parser};
+    use logos::Logos;
+
+    /// Parses and checks `input`, panicking if it couldn't be parsed
+    fn check(input: &str) -> Vec<CheckError> {
+        launch(&parser::launch(&mut Token::lexer(input)).unwrap())
+    }
+
+    #[test]
+    fn mutable() {
+        assert_eq!(check("let mut x = 1;\nx += 2;\nx = x * 2;"), vec![]);
+        assert_eq!(
+            check("let mut x = 1;\nfun f() { if true { x -= 1; } }"),
+            vec![]
+        );
+        assert_eq!(check("let a = 1;\na.b /= 2;\nself.x *= 2;"), vec![]);
+    }
+
+    #[test]
+    fn immutable() {
+        assert_eq!(
+            check("let x = 1;\nx += 2;"),
+            vec![CheckError::Immutable {
+                range: 11..12,
+                id: "x".to_string()
+            }]
+        );
+        assert_eq!(
+            check("fun f(a) { a = 2 }"),
+            vec![CheckError::Immutable {
+                range: 11..12,
+                id: "a".to_string()
+            }]
+        );
+        assert_eq!(check("let mut x = 1;\n{ let x = 2; x *= 3; }").len(), 1);
+        assert_eq!(check("let x = 1;\n\"{x += 1}\";").len(), 1);
+        assert_eq!(
+            check("let x = 1;\nx -= 2;")[0].to_string(),
+            "Cannot assign to `x` as it wasn't declared with `let mut`"
+        );
+    }
+
+    #[test]
+    fn undeclared() {
+        assert_eq!(
+            check("x /= 2;\nlet mut x = 1;"),
+            vec![CheckError::Undeclared {
+                range: 0..1,
+                id: "x".to_string()
+            }]
+        );
+        assert_eq!(
+            check("{ let mut x = 1; }\nx = 2;"),
+            vec![CheckError::Undeclared {
+                range: 19..20,
+                id: "x".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,514 @@
+//! Documentation stage ran after parsing, structuring the `---` doc comments
+//! attached to expressions and resolving the intra-doc links inside of them
+//!
+//! Doc comments start with a summary, ended by an empty `---` line, followed by
+//! an optional body and any tags:
+//!
+//! ```none
+//! --- Creates new [SomeClass] from `x` value
+//! ---
+//! --- Longer body describing the method in more detail
+//! --- @param x Value to store on the class
+//! --- @return New [SomeClass] instance
+//! --- @example
+//! --- let my_class = SomeClass::new(3);
+//! fun SomeClass::new(x) {
+//!     self.x = x;
+//! }
+//! ```
+
+use super::ast::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// Structured documentation of an [Expr] or [ClassField], parsed from the text
+/// of its doc comment
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Doc {
+    /// First paragraph of the doc, with lines joined by spaces
+    pub summary: String,
+
+    /// Remaining untagged paragraphs after the summary
+    pub body: Option<String>,
+
+    /// Parameters documented with `@param name description`
+    pub params: Vec<DocParam>,
+
+    /// Description of the returned value from `@return`
+    pub returns: Option<String>,
+
+    /// Code examples from `@example`, each continuing until the next tag
+    pub examples: Vec<String>,
+
+    /// Intra-doc links such as `[SomeClass]` in order of first appearance,
+    /// excluding any within examples or `code`
+    pub links: Vec<DocLink>,
+}
+
+/// Current section whilst parsing a [Doc], which untagged lines are added to
+#[derive(Clone, Copy)]
+enum Section {
+    Summary,
+    Body,
+    Param,
+    Return,
+    Example,
+}
+
+impl From<&str> for Doc {
+    fn from(text: &str) -> Self {
+        let mut doc = Doc::default();
+        let mut summary = vec![];
+        let mut body = vec![];
+        let mut section = Section::Summary;
+
+        for line in text.lines() {
+            let (tag, rest) = split_word(line);
+
+            section = match tag {
+                "@param" => {
+                    let (name, description) = split_word(rest);
+                    doc.params.push(DocParam {
+                        name: name.to_string(),
+                        description: description.to_string(),
+                    });
+                    Section::Param
+                }
+                "@return" => {
+                    doc.returns = Some(rest.to_string());
+                    Section::Return
+                }
+                "@example" => {
+                    doc.examples.push(rest.to_string());
+                    Section::Example
+                }
+                // unknown tags are kept as text
+                _ => add_line(&mut doc, &mut summary, &mut body, section, line),
+            }
+        }
+
+        doc.summary = summary.join(" ");
+        doc.body = Some(body.join("\n").trim().to_string()).filter(|body| !body.is_empty());
+        doc.examples
+            .iter_mut()
+            .for_each(|example| *example = example.trim().to_string());
+
+        // links are found in everything apart from the examples
+        let mut prose = vec![doc.summary.as_str()];
+        prose.extend(doc.body.as_deref());
+        prose.extend(doc.params.iter().map(|param| param.description.as_str()));
+        prose.extend(doc.returns.as_deref());
+
+        for text in prose {
+            find_links(text, &mut doc.links)
+        }
+
+        doc
+    }
+}
+
+/// Adds an untagged `line` to the current `section` of a [Doc] being parsed,
+/// returning the section to continue with
+fn add_line<'a>(
+    doc: &mut Doc,
+    summary: &mut Vec<&'a str>,
+    body: &mut Vec<&'a str>,
+    section: Section,
+    line: &'a str,
+) -> Section {
+    match section {
+        Section::Summary if line.is_empty() => return Section::Body,
+        Section::Summary => summary.push(line),
+        Section::Body => body.push(line),
+        Section::Param => join_line(&mut doc.params.last_mut().unwrap().description, line, " "),
+        Section::Return => join_line(doc.returns.as_mut().unwrap(), line, " "),
+        Section::Example => join_line(doc.examples.last_mut().unwrap(), line, "\n"),
+    }
+
+    section
+}
+
+/// Adds `line` onto the end of `text`, separated by `sep` if needed
+fn join_line(text: &mut String, line: &str, sep: &str) {
+    if !text.is_empty() {
+        text.push_str(sep)
+    }
+
+    text.push_str(line)
+}
+
+/// Splits the first word off of `text`, giving it and the trimmed remainder
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+
+    match text.find(char::is_whitespace) {
+        Some(ind) => (&text[..ind], text[ind..].trim()),
+        None => (text, ""),
+    }
+}
+
+/// Finds all intra-doc links in `text` which aren't already in `links`,
+/// skipping over `code` and markdown-style `[text](url)` links
+fn find_links(text: &str, links: &mut Vec<DocLink>) {
+    // every odd split is inside of backticks
+    for prose in text.split('`').step_by(2) {
+        let mut rest = prose;
+
+        while let Some(start) = rest.find('[') {
+            rest = &rest[start + 1..];
+
+            let end = match rest.find(']') {
+                Some(end) => end,
+                None => break,
+            };
+            let name = &rest[..end];
+
+            if is_link(name)
+                && !rest[end + 1..].starts_with('(')
+                && !links.iter().any(|link| link.name == name)
+            {
+                links.push(DocLink {
+                    name: name.to_string(),
+                    target: None,
+                })
+            }
+        }
+    }
+}
+
+/// Checks if `name` may be linked to, being identifiers joined by `::` or `.`
+fn is_link(name: &str) -> bool {
+    name.split("::").flat_map(|part| part.split('.')).all(|id| {
+        let mut chars = id.chars();
+
+        matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Parameter documented in a [Doc] with `@param`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocParam {
+    /// Name of the parameter, which should be in the function's arguments
+    pub name: String,
+
+    /// Description of the parameter
+    pub description: String,
+}
+
+/// Intra-doc link written as `[name]`, resolved to what it names by [launch]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocLink {
+    /// Name as written, such as `SomeClass`, `SomeClass::new` for creation
+    /// methods or `SomeClass.multiply` for methods and fields
+    pub name: String,
+
+    /// Byte range of the linked declaration, which is [None] if unresolved
+    pub target: Option<Range<usize>>,
+}
+
+/// Warning found whilst resolving docs, each containing the byte range of the
+/// documented item it was found at
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocWarning {
+    /// Parameter was documented which isn't in the function's arguments
+    UnknownParam { range: Range<usize>, param: String },
+
+    /// Intra-doc link didn't name any class, function, method or field
+    UnresolvedLink { range: Range<usize>, link: String },
+}
+
+impl DocWarning {
+    /// Gets the byte range this warning occurred at
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            DocWarning::UnknownParam { range, .. } | DocWarning::UnresolvedLink { range, .. } => {
+                range.clone()
+            }
+        }
+    }
+}
+
+impl fmt::Display for DocWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocWarning::UnknownParam { param, .. } => write!(
+                f,
+                "Documented parameter `{}` isn't an argument of this function",
+                param
+            ),
+            DocWarning::UnresolvedLink { link, .. } => {
+                write!(f, "Documentation link `[{}]` couldn't be resolved", link)
+            }
+        }
+    }
+}
+
+/// Resolves the links of all docs in the given expressions to the declarations
+/// they name, returning warnings for any unresolved links or documented
+/// parameters which don't exist
+pub fn launch(exprs: &mut [Expr]) -> Vec<DocWarning> {
+    let mut symbols = HashMap::new();
+    declare(exprs, &mut symbols);
+
+    let mut warnings = vec![];
+    resolve(exprs, &symbols, &mut warnings);
+    warnings
+}
+
+/// Gets the bodies of an expression which may contain declarations
+fn bodies(kind: &ExprKind) -> Vec<&[Expr]> {
+    match kind {
+        ExprKind::Block(Block(body))
+        | ExprKind::Loop(Loop { body })
+        | ExprKind::While(While { body, .. })
+        | ExprKind::Function(Function { body, .. })
+        | ExprKind::Method(Method { body, .. }) => vec![body],
+        ExprKind::If(If { segments, default }) => segments
+            .iter()
+            .map(|segment| segment.body.as_slice())
+            .chain(default.iter().map(|default| default.0.as_slice()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Mutable version of [bodies]
+fn bodies_mut(kind: &mut ExprKind) -> Vec<&mut [Expr]> {
+    match kind {
+        ExprKind::Block(Block(body))
+        | ExprKind::Loop(Loop { body })
+        | ExprKind::While(While { body, .. })
+        | ExprKind::Function(Function { body, .. })
+        | ExprKind::Method(Method { body, .. }) => vec![body],
+        ExprKind::If(If { segments, default }) => segments
+            .iter_mut()
+            .map(|segment| segment.body.as_mut_slice())
+            .chain(default.iter_mut().map(|default| default.0.as_mut_slice()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Declares all linkable names in `exprs` into `symbols`, with the byte range
+/// of their declarations
+fn declare(exprs: &[Expr], symbols: &mut HashMap<String, Range<usize>>) {
+    for expr in exprs {
+        match &expr.kind {
+            ExprKind::Class(class) => {
+                symbols.insert(class.id.name.clone(), expr.range.clone());
+
+                for field in &class.fields {
+                    symbols.insert(
+                        format!("{}.{}", class.id.name, field.id.name),
+                        field.id.range.clone(),
+                    );
+                }
+            }
+            ExprKind::Function(function) => {
+                symbols.insert(function.id.name.clone(), expr.range.clone());
+            }
+            ExprKind::Method(method) => {
+                let sep = if method.creation_method { "::" } else { "." };
+                symbols.insert(
+                    format!("{}{}{}", method.class_id.name, sep, method.id.name),
+                    expr.range.clone(),
+                );
+            }
+            _ => (),
+        }
+
+        for body in bodies(&expr.kind) {
+            declare(body, symbols)
+        }
+    }
+}
+
+/// Resolves the docs in `exprs` against the declared `symbols`, adding any
+/// problems found to `warnings`
+fn resolve(
+    exprs: &mut [Expr],
+    symbols: &HashMap<String, Range<usize>>,
+    warnings: &mut Vec<DocWarning>,
+) {
+    for expr in exprs {
+        if let Some(doc) = &mut expr.doc {
+            resolve_links(doc, &expr.range, symbols, warnings);
+
+            if let ExprKind::Function(Function { args, .. })
+            | ExprKind::Method(Method { args, .. }) = &expr.kind
+            {
+                for param in &doc.params {
+                    if !args.iter().any(|arg| arg.name == param.name) {
+                        warnings.push(DocWarning::UnknownParam {
+                            range: expr.range.clone(),
+                            param: param.name.clone(),
+                        })
+                    }
+                }
+            }
+        }
+
+        if let ExprKind::Class(class) = &mut expr.kind {
+            for field in &mut class.fields {
+                if let Some(doc) = &mut field.doc {
+                    resolve_links(doc, &field.id.range, symbols, warnings)
+                }
+            }
+        }
+
+        for body in bodies_mut(&mut expr.kind) {
+            resolve(body, symbols, warnings)
+        }
+    }
+}
+
+/// Sets the targets of all links in `doc`, warning about any unresolved ones
+/// with the `range` of the documented item
+fn resolve_links(
+    doc: &mut Doc,
+    range: &Range<usize>,
+    symbols: &HashMap<String, Range<usize>>,
+    warnings: &mut Vec<DocWarning>,
+) {
+    for link in &mut doc.links {
+        link.target = symbols.get(&link.name).cloned();
+
+        if link.target.is_none() {
+            warnings.push(DocWarning::UnresolvedLink {
+                range: range.clone(),
+                link: link.name.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::{lexer::Token, parser};
+    use logos::Logos;
+
+    /// Parses `input` and resolves its docs, panicking if it couldn't be parsed
+    fn resolved(input: &str) -> (Vec<Expr>, Vec<DocWarning>) {
+        let mut exprs = parser::launch(&mut Token::lexer(input)).unwrap();
+        let warnings = launch(&mut exprs);
+        (exprs, warnings)
+    }
+
+    #[test]
+    fn sections() {
+        assert_eq!(
+            Doc::from(
+                "Creates new [SomeClass]\nfrom `x` value\n\nLonger body\n\nover paragraphs\n@param x Value to store,\nif any\n@param y\n@return New [SomeClass]\n@example\nlet a = SomeClass::new(3);\na.x\n@example b[i]"
+            ),
+            Doc {
+                summary: "Creates new [SomeClass] from `x` value".to_string(),
+                body: Some("Longer body\n\nover paragraphs".to_string()),
+                params: vec![
+                    DocParam {
+                        name: "x".to_string(),
+                        description: "Value to store, if any".to_string()
+                    },
+                    DocParam {
+                        name: "y".to_string(),
+                        description: "".to_string()
+                    }
+                ],
+                returns: Some("New [SomeClass]".to_string()),
+                examples: vec![
+                    "let a = SomeClass::new(3);\na.x".to_string(),
+                    "b[i]".to_string()
+                ],
+                links: vec![DocLink {
+                    name: "SomeClass".to_string(),
+                    target: None
+                }]
+            }
+        );
+        assert_eq!(
+            Doc::from("Just a summary"),
+            Doc {
+                summary: "Just a summary".to_string(),
+                ..Doc::default()
+            }
+        );
+        assert_eq!(Doc::from("@unknown tag").summary, "@unknown tag");
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            Doc::from("See [A::new], [A.b], `[c]`, [site](https://x.y), [1, 2] and [A::new]")
+                .links
+                .into_iter()
+                .map(|link| link.name)
+                .collect::<Vec<String>>(),
+            vec!["A::new".to_string(), "A.b".to_string()]
+        );
+    }
+
+    #[test]
+    fn resolution() {
+        let (exprs, warnings) = resolved(
+            "class A { --- Holds [A::new]\nb };\n--- Creates new [A]\nfun A::new(x) {}\n--- Uses [A.b] and [A.c]\nfun f() {}",
+        );
+
+        assert_eq!(
+            warnings,
+            vec![DocWarning::UnresolvedLink {
+                range: 96..106,
+                link: "A.c".to_string()
+            }]
+        );
+        assert_eq!(
+            exprs[0].kind,
+            ExprKind::Class(Class {
+                id: Id {
+                    name: "A".to_string(),
+                    range: 6..7
+                },
+                fields: vec![ClassField {
+                    id: Id {
+                        name: "b".to_string(),
+                        range: 29..30
+                    },
+                    default: None,
+                    doc: Some(Doc {
+                        summary: "Holds [A::new]".to_string(),
+                        links: vec![DocLink {
+                            name: "A::new".to_string(),
+                            target: Some(exprs[1].range.clone())
+                        }],
+                        ..Doc::default()
+                    })
+                }]
+            })
+        );
+        assert_eq!(
+            exprs[1].doc.as_ref().unwrap().links[0].target,
+            Some(exprs[0].range.clone())
+        );
+        assert_eq!(exprs[2].doc.as_ref().unwrap().links[0].target, Some(29..30));
+    }
+
+    #[test]
+    fn unknown_params() {
+        let (_, warnings) = resolved(
+            "--- @param x Kept\n--- @param y Unknown\nfun A::new(x) {}\n--- @param z\nlet z = 1;",
+        );
+
+        assert_eq!(
+            warnings,
+            vec![DocWarning::UnknownParam {
+                range: 39..55,
+                param: "y".to_string()
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Documented parameter `y` isn't an argument of this function"
+        );
+    }
+}
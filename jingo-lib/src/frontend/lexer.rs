@@ -1,11 +1,134 @@
 //! Lexer/scanner stage of parsing, the first main step to parse raw characters
 //! into further parsable tokens
 
-use logos::{Lexer, Logos};
+use logos::{Filter, Lexer, Logos, Span};
+use std::borrow::Cow;
+use std::fmt;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+use unicode_script::{Script, UnicodeScript};
+
+/// Reason a [Token::Error] was lexed, stored in the lexer's extras by the
+/// callback which rejected the token as [logos] can't attach data to errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    /// String was opened but the line ended before it was closed, or the file
+    /// for `"""` and raw strings, containing the closing delimiter expected
+    UnterminatedStr(String),
+
+    /// Interpolation in a string was opened with `{` but never closed
+    UnclosedInterpolation,
+
+    /// String contained a `}` without a `{` opening an interpolation
+    UnopenedInterpolation,
+
+    /// Interpolation in a string contained no expression, i.e. `"{}"`
+    EmptyInterpolation,
+
+    /// Char was opened but the line ended before it was closed
+    UnterminatedChar,
+
+    /// Char literal contained no characters, i.e. `''`
+    EmptyChar,
+
+    /// Char literal contained more than one character, i.e. `'ab'`
+    MultiChar,
+
+    /// Integer literal was too large to fit into an [i64], found by the parser
+    /// or by the lexer for literals which don't even fit into a [u64]
+    IntTooLarge,
+
+    /// Integer literal with a radix prefix such as `0x` contained a digit
+    /// invalid for that radix, containing the digit and radix
+    InvalidDigit(char, u32),
+
+    /// Integer literal had a radix prefix such as `0x` but no digits after it,
+    /// containing the prefix
+    MissingDigits(String),
+
+    /// Block comment was opened with `/*` but never closed with `*/`
+    UnterminatedComment,
+
+    /// Escape sequence following a `\\` wasn't recognised, containing the
+    /// escape as written
+    InvalidEscape(String),
+
+    /// Characters which couldn't start any token, containing them as written
+    StrayChars(String),
+
+    /// Id or path used the reserved `this` in place of `self`
+    ReservedThis,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::UnterminatedStr(closing) => {
+                write!(f, "Unterminated string, expected a closing `{}`", closing)
+            }
+            LexError::UnclosedInterpolation => {
+                write!(f, "Unclosed `{{` in string, use `\\{{` for a literal brace")
+            }
+            LexError::UnopenedInterpolation => {
+                write!(f, "Unopened `}}` in string, use `\\}}` for a literal brace")
+            }
+            LexError::EmptyInterpolation => {
+                write!(f, "Empty `{{}}` in string, expected an expression")
+            }
+            LexError::UnterminatedChar => write!(f, "Unterminated char, expected a closing `'`"),
+            LexError::EmptyChar => write!(f, "Empty char, expected a single character"),
+            LexError::MultiChar => write!(
+                f,
+                "Char contains more than one character, use `\"` for strings"
+            ),
+            LexError::IntTooLarge => write!(f, "Integer literal too large for i64"),
+            LexError::InvalidDigit(digit, radix) => write!(
+                f,
+                "Invalid digit `{}` in {} literal",
+                digit,
+                match radix {
+                    2 => "binary",
+                    8 => "octal",
+                    _ => "hexadecimal",
+                }
+            ),
+            LexError::MissingDigits(prefix) => {
+                write!(f, "Missing digits after `{}` integer prefix", prefix)
+            }
+            LexError::UnterminatedComment => {
+                write!(f, "Unterminated block comment, expected a closing `*/`")
+            }
+            LexError::InvalidEscape(escape) => write!(f, "Invalid escape sequence `{}`", escape),
+            LexError::StrayChars(chars) => write!(f, "Unknown characters `{}`", chars),
+            LexError::ReservedThis => write!(
+                f,
+                "`this` is reserved, use `self` to refer to the current object"
+            ),
+        }
+    }
+}
+
+/// Part of an interpolated string as lexed into [Token::InterpStr]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrPart<'src> {
+    /// Literal text with its escape sequences decoded
+    Lit(Cow<'src, str>),
+
+    /// Byte span of the source of an embedded expression, excluding its
+    /// enclosing `{` and `}`
+    Expr(Span),
+}
+
+/// Lexer over [Token]s borrowing from its source
+pub(crate) type Lex<'src> = Lexer<'src, Token<'src>>;
 
 /// Lexed token from [logos], encompassing all possible tokens
-#[derive(Logos, Debug, PartialEq)]
-pub enum Token {
+///
+/// Payloads borrow from the `'src` source wherever they're written verbatim in
+/// it, only owning text which had to be decoded such as strings containing
+/// escapes, see [Token::into_owned] to detach tokens from the source
+#[derive(Logos, Debug, Clone, PartialEq)]
+#[logos(extras = Option<LexError>)]
+pub enum Token<'src> {
     // single-char
     #[token("(")]
     ParenLeft,
@@ -31,10 +154,22 @@ pub enum Token {
     // math-only symbols
     #[token("+")]
     Plus,
+    #[token("+=")]
+    PlusEquals,
     #[token("-")]
     Minus,
+    #[token("-=")]
+    MinusEquals,
+    #[token("*=")]
+    StarEquals,
+    #[token("**")]
+    StarStar,
+    #[token("%")]
+    Percent,
     #[token("/")]
     FwdSlash,
+    #[token("/=")]
+    FwdSlashEquals,
     #[token("=")]
     Equals,
     #[token("==")]
@@ -51,6 +186,18 @@ pub enum Token {
     Greater,
     #[token(">=")]
     GreaterEquals,
+    #[token("<<")]
+    LessLess,
+    #[token(">>")]
+    GreaterGreater,
+    #[token("&")]
+    Ampersand,
+    #[token("|")]
+    Pipe,
+    #[token("^")]
+    Caret,
+    #[token("~")]
+    Tilde,
 
     // keywords
     #[token("if")]
@@ -73,9 +220,13 @@ pub enum Token {
     Loop,
     #[token("while")]
     While,
+    #[token("break")]
+    Break,
+    #[token("continue")]
+    Continue,
     #[token("return")]
     Return,
-    #[token("this")]
+    #[token("self")]
     This,
     #[token("let")]
     Let,
@@ -85,69 +236,835 @@ pub enum Token {
     Fun,
 
     // literals
-    #[regex(r#"".*""#, get_str)]
-    Str(String),
-    #[regex(r"'(\\t|\\r|\\n|\\'|[^'])'", get_char)]
+    #[regex(r#""([^"\\{}\n]|\\[^u\n]|\\u(\{[^"\\{}\n]*\})?)*""#, get_str)]
+    #[regex(r#""([^"\\{}\n]|\\.)*\\?"#, unterminated_str)]
+    #[regex(r#""""("?"?([^"\\]|\\(.|\n)))*""""#, get_multiline_str)]
+    #[regex(r#""""("?"?([^"\\]|\\(.|\n)))*"?"?\\?"#, unterminated_multiline_str)]
+    #[regex(r#"r#*""#, get_raw_str)]
+    Str(Cow<'src, str>),
+    #[regex(
+        r#""([^"\\{}\n]|\\[^u\n]|\\u\{[^"\\{}\n]*\}|\\u[^{"\\\n])*[{}]"#,
+        get_interp_str
+    )]
+    /// Parts of a string containing `{}` interpolations, which may hold nested
+    /// strings of their own such as `"hi {greet("bob")}"`
+    InterpStr(Vec<StrPart<'src>>),
+    #[regex(r"'([^'\\\n]|\\.)*'", get_char)]
+    #[regex(r"'([^'\\\n]|\\.)*\\?", unterminated_char)]
     Char(char),
-    #[regex(r"[0-9]*\.[0-9]+", get_float)]
+    #[regex(r"([0-9][0-9_]*)?\.[0-9][0-9_]*([eE][+-]?[0-9][0-9_]*)?", get_float)]
+    #[regex(r"[0-9][0-9_]*[eE][+-]?[0-9][0-9_]*", get_float)]
     Float(f64),
-    #[regex(r"[0-9]+", get_int)]
-    Int(i64),
-    #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", get_id)]
-    Id(String),
-    #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*(::[a-zA-Z_][a-zA-Z0-9_]*)+", get_path)]
-    Path(Vec<String>),
+    #[regex(r"[0-9][0-9_]*", get_int)]
+    #[regex(r"0[xob][0-9a-zA-Z_]*", get_int)]
+    /// Magnitude of an integer literal, which the parser range-checks as an
+    /// [i64] once any `-` before it has been folded in
+    Int(u64),
+    #[regex(r"[\p{XID_Start}_]\p{XID_Continue}*", get_id)]
+    Id(Cow<'src, str>),
+    #[regex(
+        r"[\p{XID_Start}_]\p{XID_Continue}*(::[\p{XID_Start}_]\p{XID_Continue}*)+",
+        get_id
+    )]
+    /// Ids of a path joined by `::` as written, split on `::` to get each id
+    Path(Cow<'src, str>),
 
     // misc
     #[regex(r"---.*(\n---.*)*", get_doc)] // would be ---.*(\n+---.*)* but logos bug
-    Doc(String),
+    /// Lines of a doc comment without their `---`s, joined by line breaks
+    Doc(Cow<'src, str>),
 
     // special
     #[error]
     #[regex(r"[ \t\n\f]+|(--.*)", logos::skip)]
+    #[token("/*", block_comment)]
     Error,
 }
 
-fn get_str(lex: &mut Lexer<Token>) -> String {
+impl Token<'_> {
+    /// Converts this token into one owning all of its payloads, so it may
+    /// outlive the source it was lexed from
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::ParenLeft => Token::ParenLeft,
+            Token::ParenRight => Token::ParenRight,
+            Token::BraceLeft => Token::BraceLeft,
+            Token::BraceRight => Token::BraceRight,
+            Token::Comma => Token::Comma,
+            Token::Dot => Token::Dot,
+            Token::Semicolon => Token::Semicolon,
+            Token::Star => Token::Star,
+            Token::Static => Token::Static,
+            Token::Plus => Token::Plus,
+            Token::PlusEquals => Token::PlusEquals,
+            Token::Minus => Token::Minus,
+            Token::MinusEquals => Token::MinusEquals,
+            Token::StarEquals => Token::StarEquals,
+            Token::StarStar => Token::StarStar,
+            Token::Percent => Token::Percent,
+            Token::FwdSlash => Token::FwdSlash,
+            Token::FwdSlashEquals => Token::FwdSlashEquals,
+            Token::Equals => Token::Equals,
+            Token::EqualsEquals => Token::EqualsEquals,
+            Token::Exclaim => Token::Exclaim,
+            Token::ExclaimEquals => Token::ExclaimEquals,
+            Token::Less => Token::Less,
+            Token::LessEquals => Token::LessEquals,
+            Token::Greater => Token::Greater,
+            Token::GreaterEquals => Token::GreaterEquals,
+            Token::LessLess => Token::LessLess,
+            Token::GreaterGreater => Token::GreaterGreater,
+            Token::Ampersand => Token::Ampersand,
+            Token::Pipe => Token::Pipe,
+            Token::Caret => Token::Caret,
+            Token::Tilde => Token::Tilde,
+            Token::If => Token::If,
+            Token::And => Token::And,
+            Token::Or => Token::Or,
+            Token::Else => Token::Else,
+            Token::True => Token::True,
+            Token::False => Token::False,
+            Token::None => Token::None,
+            Token::Class => Token::Class,
+            Token::Loop => Token::Loop,
+            Token::While => Token::While,
+            Token::Break => Token::Break,
+            Token::Continue => Token::Continue,
+            Token::Return => Token::Return,
+            Token::This => Token::This,
+            Token::Let => Token::Let,
+            Token::Mut => Token::Mut,
+            Token::Fun => Token::Fun,
+            Token::Str(string) => Token::Str(Cow::Owned(string.into_owned())),
+            Token::InterpStr(parts) => Token::InterpStr(
+                parts
+                    .into_iter()
+                    .map(|part| match part {
+                        StrPart::Lit(lit) => StrPart::Lit(Cow::Owned(lit.into_owned())),
+                        StrPart::Expr(span) => StrPart::Expr(span),
+                    })
+                    .collect(),
+            ),
+            Token::Char(c) => Token::Char(c),
+            Token::Float(float) => Token::Float(float),
+            Token::Int(int) => Token::Int(int),
+            Token::Id(id) => Token::Id(Cow::Owned(id.into_owned())),
+            Token::Path(path) => Token::Path(Cow::Owned(path.into_owned())),
+            Token::Doc(doc) => Token::Doc(Cow::Owned(doc.into_owned())),
+            Token::Error => Token::Error,
+        }
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::ParenLeft => write!(f, "("),
+            Token::ParenRight => write!(f, ")"),
+            Token::BraceLeft => write!(f, "{{"),
+            Token::BraceRight => write!(f, "}}"),
+            Token::Comma => write!(f, ","),
+            Token::Dot => write!(f, "."),
+            Token::Semicolon => write!(f, ";"),
+            Token::Star => write!(f, "*"),
+            Token::Static => write!(f, "::"),
+            Token::Plus => write!(f, "+"),
+            Token::PlusEquals => write!(f, "+="),
+            Token::Minus => write!(f, "-"),
+            Token::MinusEquals => write!(f, "-="),
+            Token::StarEquals => write!(f, "*="),
+            Token::StarStar => write!(f, "**"),
+            Token::Percent => write!(f, "%"),
+            Token::FwdSlash => write!(f, "/"),
+            Token::FwdSlashEquals => write!(f, "/="),
+            Token::Equals => write!(f, "="),
+            Token::EqualsEquals => write!(f, "=="),
+            Token::Exclaim => write!(f, "!"),
+            Token::ExclaimEquals => write!(f, "!="),
+            Token::Less => write!(f, "<"),
+            Token::LessEquals => write!(f, "<="),
+            Token::Greater => write!(f, ">"),
+            Token::GreaterEquals => write!(f, ">="),
+            Token::LessLess => write!(f, "<<"),
+            Token::GreaterGreater => write!(f, ">>"),
+            Token::Ampersand => write!(f, "&"),
+            Token::Pipe => write!(f, "|"),
+            Token::Caret => write!(f, "^"),
+            Token::Tilde => write!(f, "~"),
+            Token::If => write!(f, "if"),
+            Token::And => write!(f, "and"),
+            Token::Or => write!(f, "or"),
+            Token::Else => write!(f, "else"),
+            Token::True => write!(f, "true"),
+            Token::False => write!(f, "false"),
+            Token::None => write!(f, "none"),
+            Token::Class => write!(f, "class"),
+            Token::Loop => write!(f, "loop"),
+            Token::While => write!(f, "while"),
+            Token::Break => write!(f, "break"),
+            Token::Continue => write!(f, "continue"),
+            Token::Return => write!(f, "return"),
+            Token::This => write!(f, "self"),
+            Token::Let => write!(f, "let"),
+            Token::Mut => write!(f, "mut"),
+            Token::Fun => write!(f, "fun"),
+            Token::Str(string) => write!(f, "\"{}\"", string.escape_debug()),
+            Token::InterpStr(parts) => {
+                write!(f, "\"")?;
+
+                for part in parts {
+                    match part {
+                        StrPart::Lit(lit) => write!(
+                            f,
+                            "{}",
+                            lit.escape_debug()
+                                .to_string()
+                                .replace('{', "\\{")
+                                .replace('}', "\\}")
+                        )?,
+                        StrPart::Expr(_) => write!(f, "{{..}}")?,
+                    }
+                }
+
+                write!(f, "\"")
+            }
+            Token::Char(c) => write!(f, "'{}'", c.escape_debug()),
+            Token::Float(float) => write!(f, "{}", float),
+            Token::Int(int) => write!(f, "{}", int),
+            Token::Id(id) => write!(f, "{}", id),
+            Token::Path(path) => write!(f, "{}", path),
+            Token::Doc(doc) => write!(f, "--- {}", doc),
+            Token::Error => write!(f, "unknown token"),
+        }
+    }
+}
+
+/// Invalid sequence of characters found whilst lexing with [launch], with the
+/// byte span it was found at and why it was invalid
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub span: Span,
+    pub error: LexError,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+/// Lexes the whole of `source`, giving all valid tokens along with a
+/// [Diagnostic] for every invalid one instead of stopping at the first. Any
+/// [Token::Error]s are left out of the tokens and runs of stray characters are
+/// merged into a single diagnostic
+pub fn launch(source: &str) -> (Vec<(Token<'_>, Span)>, Vec<Diagnostic>) {
+    let mut lex = Token::lexer(source);
+    let mut tokens = vec![];
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+
+    while let Some(token) = lex.next() {
+        if token != Token::Error {
+            tokens.push((token, lex.span()));
+            continue;
+        }
+
+        let span = lex.span();
+
+        match (lex.extras.take(), diagnostics.last_mut()) {
+            (Some(error), _) => diagnostics.push(Diagnostic { span, error }),
+            (
+                None,
+                Some(Diagnostic {
+                    span: last,
+                    error: LexError::StrayChars(chars),
+                }),
+            ) if last.end == span.start => {
+                chars.push_str(lex.slice());
+                last.end = span.end;
+            }
+            (None, _) => diagnostics.push(Diagnostic {
+                span,
+                error: LexError::StrayChars(lex.slice().to_string()),
+            }),
+        }
+    }
+
+    (tokens, diagnostics)
+}
+
+/// Trivia skipped by the normal lexer, only emitted by [lossless]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trivia {
+    /// Run of spaces, tabs and line breaks
+    Whitespace,
+
+    /// Line comment starting with `--`, excluding the line break after it
+    LineComment,
+
+    /// Possibly nested block comment from `/*` to `*/`
+    BlockComment,
+}
+
+/// Lexed item of the lossless lexing mode from [lossless], being either a
+/// normal [Token] or [Trivia]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lossless<'src> {
+    Token(Token<'src>),
+    Trivia(Trivia),
+}
+
+/// Lexes the given `source` without skipping any of it, giving every [Token]
+/// along with the [Trivia] around them. The spans of the output cover the
+/// whole of `source` in order, so tools such as formatters may reproduce the
+/// original source exactly
+pub fn lossless(source: &str) -> Vec<(Lossless<'_>, Span)> {
+    let mut lex = Token::lexer(source);
+    let mut output = vec![];
+    let mut end = 0;
+
+    while let Some(token) = lex.next() {
+        trivia(source, end..lex.span().start, &mut output);
+        output.push((Lossless::Token(token), lex.span()));
+        end = lex.span().end;
+    }
+
+    trivia(source, end..source.len(), &mut output);
+    output
+}
+
+/// Splits the skipped `gap` of `source` between two tokens into [Trivia],
+/// adding them to `output`
+fn trivia(source: &str, gap: Span, output: &mut Vec<(Lossless<'_>, Span)>) {
+    let mut start = gap.start;
+
+    while start < gap.end {
+        let rest = &source[start..gap.end];
+        let (kind, len) = if rest.starts_with("--") {
+            (Trivia::LineComment, rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            // block comments in gaps are always closed as errors are tokens
+            let len = block_comment_len(comment).map_or(rest.len(), |len| len + 2);
+            (Trivia::BlockComment, len)
+        } else {
+            // only whitespace is skipped otherwise, but any other character is
+            // still consumed so the whole gap is always covered
+            let len = match rest.find(|c: char| !c.is_whitespace()) {
+                Some(0) => rest.chars().next().unwrap().len_utf8(),
+                Some(len) => len,
+                None => rest.len(),
+            };
+            (Trivia::Whitespace, len)
+        };
+
+        output.push((Lossless::Trivia(kind), start..start + len));
+        start += len;
+    }
+}
+
+/// Lint for an identifier mixing characters from multiple scripts, which may
+/// be visually confused with another identifier, i.e. a latin `paypal` with a
+/// cyrillic `а` in place of the first `a`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixedScript {
+    /// Byte span of the identifier
+    pub span: Span,
+
+    /// Identifier as written
+    pub id: String,
+
+    /// Scripts mixed in the identifier, in order of first appearance
+    pub scripts: Vec<Script>,
+}
+
+impl fmt::Display for MixedScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self
+            .scripts
+            .iter()
+            .map(|script| script.full_name())
+            .collect();
+        let (last, rest) = names.split_last().unwrap();
+
+        write!(
+            f,
+            "Identifier `{}` mixes {} and {} characters, so it may be confused with another identifier",
+            self.id,
+            rest.join(", "),
+            last
+        )
+    }
+}
+
+/// Finds all identifiers in the given `source` which mix scripts, see
+/// [MixedScript] for more info
+pub fn mixed_scripts(source: &str) -> Vec<MixedScript> {
+    let mut lex = Token::lexer(source);
+    let mut output = vec![];
+
+    while let Some(token) = lex.next() {
+        if !matches!(token, Token::Id(_) | Token::Path(_)) {
+            continue;
+        }
+
+        let mut start = lex.span().start;
+
+        for id in lex.slice().split("::") {
+            let scripts = id_scripts(id);
+
+            if scripts.len() > 1 && !is_cjk(&scripts) {
+                output.push(MixedScript {
+                    span: start..start + id.len(),
+                    id: id.to_string(),
+                    scripts,
+                })
+            }
+
+            start += id.len() + 2; // skip over `::`
+        }
+    }
+
+    output
+}
+
+/// Gets the unique scripts used in an identifier, ignoring characters such as
+/// digits and underscores which are common to all scripts
+fn id_scripts(id: &str) -> Vec<Script> {
+    let mut scripts = vec![];
+
+    for script in id.chars().map(|c| c.script()) {
+        if !matches!(script, Script::Common | Script::Inherited) && !scripts.contains(&script) {
+            scripts.push(script)
+        }
+    }
+
+    scripts
+}
+
+/// Checks if the `scripts` of an identifier are a mix which is normal in
+/// Japanese, Korean or Chinese writing, such as Han with Hiragana
+fn is_cjk(scripts: &[Script]) -> bool {
+    const GROUPS: [&[Script]; 3] = [
+        &[Script::Han, Script::Hiragana, Script::Katakana],
+        &[Script::Han, Script::Hangul],
+        &[Script::Han, Script::Bopomofo],
+    ];
+
+    GROUPS
+        .iter()
+        .any(|group| scripts.iter().all(|script| group.contains(script)))
+}
+
+fn get_str<'src>(lex: &mut Lex<'src>) -> Option<Cow<'src, str>> {
+    let slice = lex.slice();
+    let result = unescape(&slice[1..slice.len() - 1]);
+
+    or_extras(lex, result)
+}
+
+/// Lexes the rest of an interpolated string after the first `{` or `}` in it,
+/// finding the `"` closing it on the same line whilst skipping over any strings
+/// nested inside of its embedded expressions
+fn get_interp_str<'src>(lex: &mut Lex<'src>) -> Option<Vec<StrPart<'src>>> {
+    let start = lex.span().start + 1;
+    let input = &lex.source()[start..];
+
+    let result = match str_len(input) {
+        Ok(len) => {
+            lex.bump(start + len - lex.span().end);
+            interp_parts(&input[..len - 1], start)
+        }
+        Err(err) => {
+            // strings can't span lines so lexing may continue on the next one
+            let rest = lex.remainder();
+            lex.bump(rest.find('\n').unwrap_or(rest.len()));
+            Err(err)
+        }
+    };
+
+    or_extras(lex, result)
+}
+
+/// Gets the length of the contents of a string after its opening `"`, up to
+/// and including the `"` closing it before the end of the line
+fn str_len(input: &str) -> Result<usize, LexError> {
+    let mut ind = 0;
+
+    while let Some(c) = input[ind..].chars().next() {
+        ind += c.len_utf8();
+
+        match c {
+            '"' => return Ok(ind),
+            '\n' => break,
+            '\\' if !input[ind..].starts_with('\n') => {
+                let rest = &input[ind..];
+
+                // braces of unicode escapes don't open interpolations
+                ind += match rest.find(&['}', '"'][..]) {
+                    Some(end) if rest.starts_with("u{") && rest[end..].starts_with('}') => end + 1,
+                    _ => rest.chars().next().map_or(0, char::len_utf8),
+                }
+            }
+            '{' => ind += interp_len(&input[ind..])?,
+            _ => (),
+        }
+    }
+
+    Err(LexError::UnterminatedStr("\"".to_string()))
+}
+
+/// Gets the length of an embedded expression after the `{` opening it, up to
+/// and including the `}` closing it, skipping over any blocks, strings and
+/// chars inside of it which may contain braces of their own
+fn interp_len(input: &str) -> Result<usize, LexError> {
+    let mut ind = 0;
+    let mut depth = 1;
+
+    while let Some(c) = input[ind..].chars().next() {
+        ind += c.len_utf8();
+
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Ok(ind),
+            '\n' => break,
+            '}' => depth -= 1,
+            '"' => ind += str_len(&input[ind..]).map_err(|_| LexError::UnclosedInterpolation)?,
+            '\'' => {
+                let mut chars = input[ind..].chars();
+
+                if chars.next() == Some('\\') {
+                    chars.next();
+                }
+
+                if chars.as_str().starts_with('\'') {
+                    ind = input.len() - chars.as_str().len() + 1;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Err(LexError::UnclosedInterpolation)
+}
+
+/// Splits the contents of an interpolated string, which has had its quotes
+/// removed, into its literal parts and the spans of its embedded expressions.
+/// The `offset` is the byte position of `input` inside of the source
+fn interp_parts(input: &str, offset: usize) -> Result<Vec<StrPart<'_>>, LexError> {
+    let mut parts = vec![];
+    let mut lit = String::new();
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => lit.push(unescape_char(&mut chars)?),
+            '{' => {
+                let start = input.len() - chars.as_str().len();
+                let end = start + interp_len(chars.as_str())? - 1;
+
+                chars = input[end + 1..].chars();
+
+                if input[start..end].trim().is_empty() {
+                    return Err(LexError::EmptyInterpolation);
+                } else if !lit.is_empty() {
+                    parts.push(StrPart::Lit(Cow::Owned(std::mem::take(&mut lit))));
+                }
+
+                parts.push(StrPart::Expr(offset + start..offset + end));
+            }
+            '}' => return Err(LexError::UnopenedInterpolation),
+            c => lit.push(c),
+        }
+    }
+
+    if !lit.is_empty() {
+        parts.push(StrPart::Lit(Cow::Owned(lit)));
+    }
+
+    Ok(parts)
+}
+
+fn unterminated_str<'src>(lex: &mut Lex<'src>) -> Option<Cow<'src, str>> {
+    lex.extras = Some(LexError::UnterminatedStr("\"".to_string()));
+    None
+}
+
+fn get_multiline_str<'src>(lex: &mut Lex<'src>) -> Option<Cow<'src, str>> {
     let slice = lex.slice();
-    slice[1..slice.len() - 1].to_string()
+    let result = match strip_indent(&slice[3..slice.len() - 3]) {
+        Cow::Borrowed(input) => unescape(input),
+        Cow::Owned(input) => unescape(&input).map(|output| Cow::Owned(output.into_owned())),
+    };
+
+    or_extras(lex, result)
 }
 
-fn get_char(lex: &mut Lexer<Token>) -> Option<char> {
-    lex.slice().parse().ok()
+fn unterminated_multiline_str<'src>(lex: &mut Lex<'src>) -> Option<Cow<'src, str>> {
+    lex.extras = Some(LexError::UnterminatedStr("\"\"\"".to_string()));
+    None
 }
 
-fn get_float(lex: &mut Lexer<Token>) -> Option<f64> {
-    lex.slice().parse().ok()
+/// Strips the contents of a multi-line string, removing the line break after
+/// the opening `"""`, the line holding the closing `"""` if it's otherwise
+/// blank and the indentation common to all non-blank lines
+fn strip_indent(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\n').unwrap_or(input);
+    let input = match input.rfind('\n') {
+        Some(ind) if input[ind + 1..].trim().is_empty() => &input[..ind],
+        _ => input,
+    };
+
+    let indent = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    if indent == 0 {
+        return Cow::Borrowed(input);
+    }
+
+    Cow::Owned(
+        input
+            .split('\n')
+            .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    )
 }
 
+/// Lexes the rest of a raw string after its opening `r"`, `r#"` or so on,
+/// finding the `"` followed by the same number of `#`s closing it
+fn get_raw_str<'src>(lex: &mut Lex<'src>) -> Option<Cow<'src, str>> {
+    let closing = format!("\"{}", &lex.slice()[1..lex.slice().len() - 1]);
 
-fn get_id(lex: &mut Lexer<Token>) -> String {
-    lex.slice().to_string()
+    match lex.remainder().find(&closing) {
+        Some(len) => {
+            let string = &lex.remainder()[..len];
+            lex.bump(len + closing.len());
+            Some(Cow::Borrowed(string))
+        }
+        None => {
+            lex.bump(lex.remainder().len());
+            lex.extras = Some(LexError::UnterminatedStr(closing));
+            None
+        }
+    }
 }
-fn get_path(lex: &mut Lexer<Token>) -> Vec<String> {
-    lex.slice().split("::").map(|id| id.to_string()).collect()
+
+/// Converts the `result` of a callback into an [Option] for [logos], storing
+/// the error in the lexer's extras if there was one so it may be reported
+fn or_extras<T>(lex: &mut Lex, result: Result<T, LexError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            lex.extras = Some(err);
+            None
+        }
+    }
 }
 
+/// Decodes all escape sequences inside of the given string or char literal
+/// contents, which has had its quotes removed, borrowing `input` if it has none
+fn unescape(input: &str) -> Result<Cow<'_, str>, LexError> {
+    if !input.contains('\\') {
+        return Ok(Cow::Borrowed(input));
+    }
 
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
 
-fn get_int(lex: &mut Lexer<Token>) -> Option<i64> {
-    lex.slice().parse().ok()
+    while let Some(c) = chars.next() {
+        output.push(if c == '\\' {
+            unescape_char(&mut chars)?
+        } else {
+            c
+        })
+    }
+
+    Ok(Cow::Owned(output))
+}
+
+/// Decodes a single escape sequence directly following a `\\` which has
+/// already been consumed from `chars`
+fn unescape_char(chars: &mut std::str::Chars) -> Result<char, LexError> {
+    Ok(match chars.next() {
+        Some('n') => '\n',
+        Some('t') => '\t',
+        Some('r') => '\r',
+        Some('\\') => '\\',
+        Some('"') => '"',
+        Some('\'') => '\'',
+        Some('{') => '{',
+        Some('}') => '}',
+        Some('u') => unescape_unicode(chars)?,
+        Some(other) => return Err(LexError::InvalidEscape(format!("\\{}", other))),
+        None => return Err(LexError::InvalidEscape("\\".to_string())),
+    })
+}
+
+/// Decodes the `{XXXX}` part of a `\u{XXXX}` unicode escape, consuming up to
+/// and including the closing `}`
+fn unescape_unicode(chars: &mut std::str::Chars) -> Result<char, LexError> {
+    let rest = chars.as_str();
+    let end = match rest.find('}') {
+        Some(end) if rest.starts_with('{') => end,
+        _ => return Err(LexError::InvalidEscape("\\u".to_string())),
+    };
+    let hex = &rest[1..end];
+
+    *chars = rest[end + 1..].chars();
+
+    Some(hex)
+        .filter(|hex| (1..=6).contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .and_then(std::char::from_u32)
+        .ok_or_else(|| LexError::InvalidEscape(format!("\\u{}", &rest[..=end])))
+}
+
+fn get_char(lex: &mut Lex) -> Option<char> {
+    let slice = lex.slice();
+    let result = unescape(&slice[1..slice.len() - 1]).and_then(|string| {
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            (None, _) => Err(LexError::EmptyChar),
+            (Some(_), Some(_)) => Err(LexError::MultiChar),
+        }
+    });
+
+    or_extras(lex, result)
+}
+
+fn unterminated_char(lex: &mut Lex) -> Option<char> {
+    lex.extras = Some(LexError::UnterminatedChar);
+    None
+}
+
+fn get_float(lex: &mut Lex) -> Option<f64> {
+    without_underscores(lex.slice()).parse().ok()
+}
+
+/// Removes the `_` separators from a number literal, only allocating if it
+/// contains any
+fn without_underscores(input: &str) -> Cow<'_, str> {
+    if input.contains('_') {
+        Cow::Owned(input.replace('_', ""))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Skips the rest of a block comment after its opening `/*`, which may contain
+/// nested block comments, or gives an error if it was never closed
+fn block_comment(lex: &mut Lex) -> Filter<()> {
+    match block_comment_len(lex.remainder()) {
+        Some(len) => {
+            lex.bump(len);
+            Filter::Skip
+        }
+        None => {
+            lex.bump(lex.remainder().len());
+            lex.extras = Some(LexError::UnterminatedComment);
+            Filter::Emit(())
+        }
+    }
 }
 
-fn get_doc(lex: &mut Lexer<Token>) -> String {
-    lex.slice()
-        .split('\n')
-        .filter(|l| l.len() != 0)
-        .map(|l| l[3..].trim())
-        .collect::<Vec<&str>>()
-        .join("\n")
+/// Gets the length of the rest of a block comment after its opening `/*` up to
+/// and including the `*/` closing it, or [None] if it's never closed
+fn block_comment_len(input: &str) -> Option<usize> {
+    let mut depth = 1;
+    let mut len = 0;
+
+    while depth > 0 {
+        let rest = &input[len..];
+        let (ind, opens) = match (rest.find("/*"), rest.find("*/")) {
+            (Some(open), Some(close)) if open < close => (open, true),
+            (_, Some(close)) => (close, false),
+            (Some(open), None) => (open, true),
+            (None, None) => return None,
+        };
+
+        len += ind + 2;
+        depth = if opens { depth + 1 } else { depth - 1 };
+    }
+
+    Some(len)
+}
+
+/// Gets an id or path, only allocating if it has to be normalised into NFC
+fn get_id<'src>(lex: &mut Lex<'src>) -> Option<Cow<'src, str>> {
+    let slice = lex.slice();
+
+    if slice.split("::").any(|id| id == "this") {
+        lex.extras = Some(LexError::ReservedThis);
+        None
+    } else if is_nfc(slice) {
+        Some(Cow::Borrowed(slice))
+    } else {
+        Some(Cow::Owned(slice.nfc().collect()))
+    }
+}
+
+fn get_int(lex: &mut Lex) -> Option<u64> {
+    let slice = lex.slice();
+    let (radix, digits) = match slice.get(..2) {
+        Some("0x") => (16, &slice[2..]),
+        Some("0o") => (8, &slice[2..]),
+        Some("0b") => (2, &slice[2..]),
+        _ => (10, slice),
+    };
+    let digits = without_underscores(digits);
+
+    let result = if let Some(digit) = digits.chars().find(|c| !c.is_digit(radix)) {
+        Err(LexError::InvalidDigit(digit, radix))
+    } else if digits.is_empty() {
+        Err(LexError::MissingDigits(slice[..2].to_string()))
+    } else {
+        // only overflows are possible as the digits have been checked above
+        u64::from_str_radix(&digits, radix).map_err(|_| LexError::IntTooLarge)
+    };
+
+    or_extras(lex, result)
+}
+
+/// Gets the text of a doc comment without its `---`s, only allocating if it
+/// spans multiple lines which have to be joined
+fn get_doc<'src>(lex: &mut Lex<'src>) -> Cow<'src, str> {
+    let slice = lex.slice();
+
+    if !slice.contains('\n') {
+        return Cow::Borrowed(slice[3..].trim());
+    }
+
+    Cow::Owned(
+        slice
+            .split('\n')
+            .filter(|l| !l.is_empty())
+            .map(|l| l[3..].trim())
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Asserts that each input lexes into a single [Token::Error] spanning the
+    /// whole input, with the error given alongside it
+    fn assert_lex_errors(errors: &[(&str, LexError)]) {
+        for (input, error) in errors {
+            let mut lex = Token::lexer(input);
+
+            assert_eq!(lex.next().unwrap(), Token::Error, "lexing {}", input);
+            assert_eq!(lex.span(), 0..input.len(), "lexing {}", input);
+            assert_eq!(lex.extras.as_ref(), Some(error), "lexing {}", input);
+        }
+    }
+
     #[test]
     fn basic() {
         let mut lex =
@@ -161,11 +1078,532 @@ mod tests {
         assert_eq!(lex.next().unwrap(), Token::Int(1));
         assert_eq!(lex.next().unwrap(), Token::Float(0.01));
         assert_eq!(lex.next().unwrap(), Token::BraceRight);
-        assert_eq!(lex.next().unwrap(), Token::Id("my_id".to_string()));
-        assert_eq!(lex.next().unwrap(), Token::Doc("docstring".to_string()));
+        assert_eq!(lex.next().unwrap(), Token::Id("my_id".into()));
+        assert_eq!(lex.next().unwrap(), Token::Doc("docstring".into()));
         assert_eq!(lex.next().unwrap(), Token::True);
     }
 
+    #[test]
+    fn keywords() {
+        let mut lex = Token::lexer("self none this a::this");
+
+        assert_eq!(lex.next().unwrap(), Token::This);
+        assert_eq!(lex.next().unwrap(), Token::None);
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.extras.take(), Some(LexError::ReservedThis));
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.extras.take(), Some(LexError::ReservedThis));
+    }
+
+    #[test]
+    fn zero_copy() {
+        let source = "x café::x \"hi\" \"a\\n\" e\u{301} --- doc\n; --- a\n--- b\n";
+        let tokens: Vec<Token> = Token::lexer(source).collect();
+        let borrowed: Vec<bool> = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Id(text) | Token::Path(text) | Token::Str(text) | Token::Doc(text) => {
+                    Some(matches!(text, Cow::Borrowed(_)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // only escaped strings, unnormalised ids and multi-line docs are owned
+        assert_eq!(borrowed, vec![true, true, true, false, false, true, false]);
+
+        let owned: Vec<Token<'static>> = tokens.iter().cloned().map(Token::into_owned).collect();
+        assert_eq!(owned, tokens);
+    }
+
+    #[test]
+    fn unicode_ids() {
+        let mut lex = Token::lexer("größe 名前 _ß9 café::naïve");
+
+        assert_eq!(lex.next().unwrap(), Token::Id("größe".into()));
+        assert_eq!(lex.next().unwrap(), Token::Id("名前".into()));
+        assert_eq!(lex.next().unwrap(), Token::Id("_ß9".into()));
+        assert_eq!(lex.next().unwrap(), Token::Path("café::naïve".into()));
+        assert_eq!(Token::lexer("9a").next().unwrap(), Token::Int(9));
+    }
+
+    #[test]
+    fn nfc_ids() {
+        assert_eq!(
+            Token::lexer("e\u{301}").next().unwrap(),
+            Token::lexer("\u{e9}").next().unwrap()
+        );
+        assert_eq!(
+            Token::lexer("cafe\u{301}::x").next().unwrap(),
+            Token::Path("café::x".into())
+        );
+    }
+
+    #[test]
+    fn mixed_script_ids() {
+        assert_eq!(
+            mixed_scripts("let p\u{430}ypal = 1;"),
+            vec![MixedScript {
+                span: 4..11,
+                id: "p\u{430}ypal".to_string(),
+                scripts: vec![Script::Latin, Script::Cyrillic]
+            }]
+        );
+        assert_eq!(mixed_scripts("a::b\u{3bf}x").len(), 1);
+        assert_eq!(mixed_scripts("a::b\u{3bf}x")[0].span, 3..7);
+        assert_eq!(
+            mixed_scripts("p\u{430}ypal")[0].to_string(),
+            "Identifier `p\u{430}ypal` mixes Latin and Cyrillic characters, so it may be confused with another identifier"
+        );
+        assert_eq!(
+            mixed_scripts("日本語の名前 カタカナ 한국어 paypal_2 привет"),
+            vec![]
+        );
+        assert_eq!(mixed_scripts("\"p\u{430}ypal\" -- p\u{430}ypal"), vec![]);
+    }
+
+    #[test]
+    fn compound_assignments() {
+        let mut lex = Token::lexer("+= -= *= /= + =");
+
+        assert_eq!(lex.next().unwrap(), Token::PlusEquals);
+        assert_eq!(lex.next().unwrap(), Token::MinusEquals);
+        assert_eq!(lex.next().unwrap(), Token::StarEquals);
+        assert_eq!(lex.next().unwrap(), Token::FwdSlashEquals);
+        assert_eq!(lex.next().unwrap(), Token::Plus);
+        assert_eq!(lex.next().unwrap(), Token::Equals);
+    }
+
+    #[test]
+    fn extended_ops() {
+        let mut lex = Token::lexer("% ** * & | ^ ~ << >> < >");
+
+        assert_eq!(lex.next().unwrap(), Token::Percent);
+        assert_eq!(lex.next().unwrap(), Token::StarStar);
+        assert_eq!(lex.next().unwrap(), Token::Star);
+        assert_eq!(lex.next().unwrap(), Token::Ampersand);
+        assert_eq!(lex.next().unwrap(), Token::Pipe);
+        assert_eq!(lex.next().unwrap(), Token::Caret);
+        assert_eq!(lex.next().unwrap(), Token::Tilde);
+        assert_eq!(lex.next().unwrap(), Token::LessLess);
+        assert_eq!(lex.next().unwrap(), Token::GreaterGreater);
+        assert_eq!(lex.next().unwrap(), Token::Less);
+        assert_eq!(lex.next().unwrap(), Token::Greater);
+    }
+
+    #[test]
+    fn strs() {
+        let mut lex = Token::lexer(r#""a" + "b" "" "-- not a comment""#);
+
+        assert_eq!(lex.next().unwrap(), Token::Str("a".into()));
+        assert_eq!(lex.next().unwrap(), Token::Plus);
+        assert_eq!(lex.next().unwrap(), Token::Str("b".into()));
+        assert_eq!(lex.next().unwrap(), Token::Str("".into()));
+        assert_eq!(lex.next().unwrap(), Token::Str("-- not a comment".into()));
+        assert_eq!(lex.next(), None);
+    }
+
+    #[test]
+    fn str_escapes() {
+        assert_eq!(
+            Token::lexer(r#""line\n\ttab \\ \"quoted\" \u{41}\u{1F600}""#)
+                .next()
+                .unwrap(),
+            Token::Str("line\n\ttab \\ \"quoted\" A\u{1F600}".into())
+        );
+        assert_eq!(
+            Token::lexer(r#""\"" "after""#).collect::<Vec<_>>(),
+            vec![Token::Str("\"".into()), Token::Str("after".into())]
+        );
+    }
+
+    #[test]
+    fn str_errors() {
+        let errors = [
+            (r#""abc"#, LexError::UnterminatedStr("\"".to_string())),
+            (r#""abc\""#, LexError::UnterminatedStr("\"".to_string())),
+            (r#""\q""#, LexError::InvalidEscape(r"\q".to_string())),
+            (r#""\u41""#, LexError::InvalidEscape(r"\u".to_string())),
+            (r#""\u{}""#, LexError::InvalidEscape(r"\u{}".to_string())),
+            (
+                r#""\u{110000}""#,
+                LexError::InvalidEscape(r"\u{110000}".to_string()),
+            ),
+            (
+                r#""\u{+41}""#,
+                LexError::InvalidEscape(r"\u{+41}".to_string()),
+            ),
+        ];
+
+        assert_lex_errors(&errors);
+
+        assert_eq!(
+            LexError::InvalidEscape(r"\q".to_string()).to_string(),
+            "Invalid escape sequence `\\q`"
+        );
+    }
+
+    #[test]
+    fn multiline_strs() {
+        assert_eq!(
+            Token::lexer(
+                "\"\"\"\n    SELECT *\n      FROM users\n\n    WHERE id = \"{id}\"\n    \"\"\""
+            )
+            .next()
+            .unwrap(),
+            Token::Str("SELECT *\n  FROM users\n\nWHERE id = \"{id}\"".into())
+        );
+        assert_eq!(
+            Token::lexer("\"\"\"one \"two\" \"\"three\"\"\\t\"\"\" x").collect::<Vec<_>>(),
+            vec![
+                Token::Str("one \"two\" \"\"three\"\"\t".into()),
+                Token::Id("x".into())
+            ]
+        );
+        assert_eq!(
+            Token::lexer("\"\"\"\"\"\"").next().unwrap(),
+            Token::Str("".into())
+        );
+        assert_eq!(
+            Token::lexer("\"\" \"\"").collect::<Vec<_>>(),
+            vec![Token::Str("".into()), Token::Str("".into())]
+        );
+
+        let mut lex = Token::lexer("\"\"\"\n  abc\"\"");
+
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.span(), 0..11);
+        assert_eq!(
+            lex.extras,
+            Some(LexError::UnterminatedStr("\"\"\"".to_string()))
+        );
+    }
+
+    #[test]
+    fn raw_strs() {
+        let mut lex = Token::lexer(r###"r"C:\path\{x}" r#"say "hi"\n"# r##"a "# b"## r"" rest"###);
+
+        assert_eq!(lex.next().unwrap(), Token::Str(r"C:\path\{x}".into()));
+        assert_eq!(lex.next().unwrap(), Token::Str(r#"say "hi"\n"#.into()));
+        assert_eq!(lex.next().unwrap(), Token::Str(r##"a "# b"##.into()));
+        assert_eq!(lex.next().unwrap(), Token::Str("".into()));
+        assert_eq!(lex.next().unwrap(), Token::Id("rest".into()));
+        assert_eq!(lex.next(), None);
+
+        let mut lex = Token::lexer(r##"r#"abc" 1"##);
+
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.span(), 0..9);
+        assert_eq!(
+            lex.extras,
+            Some(LexError::UnterminatedStr("\"#".to_string()))
+        );
+        assert_eq!(lex.next(), None);
+    }
+
+    #[test]
+    fn interp_strs() {
+        let mut lex = Token::lexer(r#""hello {name}, you are {age + 1}" "\{literal\}" "{ {1} }""#);
+
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::InterpStr(vec![
+                StrPart::Lit("hello ".into()),
+                StrPart::Expr(8..12),
+                StrPart::Lit(", you are ".into()),
+                StrPart::Expr(24..31)
+            ])
+        );
+        assert_eq!(lex.next().unwrap(), Token::Str("{literal}".into()));
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::InterpStr(vec![StrPart::Expr(50..55)])
+        );
+        assert_eq!(lex.next(), None);
+
+        let mut lex = Token::lexer(r#""hi {greet("bob")}" "{ "}" }" "{ '}' }" "\u{41}{x}" 1"#);
+
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::InterpStr(vec![StrPart::Lit("hi ".into()), StrPart::Expr(5..17)])
+        );
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::InterpStr(vec![StrPart::Expr(22..27)])
+        );
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::InterpStr(vec![StrPart::Expr(32..37)])
+        );
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::InterpStr(vec![StrPart::Lit("A".into()), StrPart::Expr(48..49)])
+        );
+        assert_eq!(lex.next().unwrap(), Token::Int(1));
+    }
+
+    #[test]
+    fn interp_str_errors() {
+        let errors = [
+            (r#""hello {name""#, LexError::UnclosedInterpolation),
+            (r#""{{a}""#, LexError::UnclosedInterpolation),
+            (r#""hello }""#, LexError::UnopenedInterpolation),
+            (r#""{a}}""#, LexError::UnopenedInterpolation),
+            (r#""a { } b""#, LexError::EmptyInterpolation),
+            (r#""{ "a }""#, LexError::UnclosedInterpolation),
+            (r#""a {x} b"#, LexError::UnterminatedStr("\"".to_string())),
+            (r#""a {"x"#, LexError::UnclosedInterpolation),
+        ];
+
+        assert_lex_errors(&errors);
+    }
+
+    #[test]
+    fn chars() {
+        let mut lex = Token::lexer(r#"'a' '\n' '\'' '\u{1F600}' '\\' '\t' '\r' '\"' 'é'"#);
+
+        assert_eq!(lex.next().unwrap(), Token::Char('a'));
+        assert_eq!(lex.next().unwrap(), Token::Char('\n'));
+        assert_eq!(lex.next().unwrap(), Token::Char('\''));
+        assert_eq!(lex.next().unwrap(), Token::Char('\u{1F600}'));
+        assert_eq!(lex.next().unwrap(), Token::Char('\\'));
+        assert_eq!(lex.next().unwrap(), Token::Char('\t'));
+        assert_eq!(lex.next().unwrap(), Token::Char('\r'));
+        assert_eq!(lex.next().unwrap(), Token::Char('"'));
+        assert_eq!(lex.next().unwrap(), Token::Char('é'));
+        assert_eq!(lex.next(), None);
+    }
+
+    #[test]
+    fn char_errors() {
+        let errors = [
+            ("''", LexError::EmptyChar),
+            ("'ab'", LexError::MultiChar),
+            (r"'\n\n'", LexError::MultiChar),
+            ("'a", LexError::UnterminatedChar),
+            (r"'\q'", LexError::InvalidEscape(r"\q".to_string())),
+        ];
+
+        assert_lex_errors(&errors);
+
+        let mut lex = Token::lexer("'a\nb'");
+
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.extras, Some(LexError::UnterminatedChar));
+        assert_eq!(lex.span(), 0..2);
+    }
+
+    #[test]
+    fn nums() {
+        let mut lex =
+            Token::lexer("0xFF 0b1010 0o17 0x_dead_BEEF 1_000_000 1e-9 2.5E3 1_0.2_5 .5 3e+2_0");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(255));
+        assert_eq!(lex.next().unwrap(), Token::Int(10));
+        assert_eq!(lex.next().unwrap(), Token::Int(15));
+        assert_eq!(lex.next().unwrap(), Token::Int(0xdead_beef));
+        assert_eq!(lex.next().unwrap(), Token::Int(1_000_000));
+        assert_eq!(lex.next().unwrap(), Token::Float(1e-9));
+        assert_eq!(lex.next().unwrap(), Token::Float(2.5e3));
+        assert_eq!(lex.next().unwrap(), Token::Float(10.25));
+        assert_eq!(lex.next().unwrap(), Token::Float(0.5));
+        assert_eq!(lex.next().unwrap(), Token::Float(3e20));
+        assert_eq!(lex.next(), None);
+
+        let mut lex = Token::lexer("18446744073709551615 1e x");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(u64::MAX));
+        assert_eq!(lex.next().unwrap(), Token::Int(1));
+        assert_eq!(lex.next().unwrap(), Token::Id("e".into()));
+    }
+
+    #[test]
+    fn num_errors() {
+        let errors = [
+            ("18446744073709551616", LexError::IntTooLarge),
+            ("0x1_0000_0000_0000_0000", LexError::IntTooLarge),
+            ("0b102", LexError::InvalidDigit('2', 2)),
+            ("0o8", LexError::InvalidDigit('8', 8)),
+            ("0xFG", LexError::InvalidDigit('G', 16)),
+            ("0x_", LexError::MissingDigits("0x".to_string())),
+        ];
+
+        assert_lex_errors(&errors);
+
+        assert_eq!(
+            LexError::IntTooLarge.to_string(),
+            "Integer literal too large for i64"
+        );
+        assert_eq!(
+            LexError::InvalidDigit('2', 2).to_string(),
+            "Invalid digit `2` in binary literal"
+        );
+    }
+
+    #[test]
+    fn block_comments() {
+        let mut lex = Token::lexer(
+            "1 /* skipped */ 2 /* outer /* inner */ still -- skipped\n */ 3 /**/ 4 /* --- not a doc */\n--- doc\n5",
+        );
+
+        assert_eq!(lex.next().unwrap(), Token::Int(1));
+        assert_eq!(lex.next().unwrap(), Token::Int(2));
+        assert_eq!(lex.next().unwrap(), Token::Int(3));
+        assert_eq!(lex.next().unwrap(), Token::Int(4));
+        assert_eq!(lex.next().unwrap(), Token::Doc("doc".into()));
+        assert_eq!(lex.next().unwrap(), Token::Int(5));
+        assert_eq!(lex.next(), None);
+
+        let mut lex = Token::lexer("x / y -- /* not a block comment\nz");
+
+        assert_eq!(lex.next().unwrap(), Token::Id("x".into()));
+        assert_eq!(lex.next().unwrap(), Token::FwdSlash);
+        assert_eq!(lex.next().unwrap(), Token::Id("y".into()));
+        assert_eq!(lex.next().unwrap(), Token::Id("z".into()));
+    }
+
+    #[test]
+    fn block_comment_errors() {
+        for input in ["1 /* open", "1 /* outer /* inner */"].iter() {
+            let mut lex = Token::lexer(input);
+
+            assert_eq!(lex.next().unwrap(), Token::Int(1));
+            assert_eq!(lex.next().unwrap(), Token::Error);
+            assert_eq!(lex.span(), 2..input.len());
+            assert_eq!(lex.extras, Some(LexError::UnterminatedComment));
+            assert_eq!(lex.next(), None);
+        }
+    }
+
+    #[test]
+    fn all_diagnostics() {
+        let (tokens, diagnostics) = launch("let $$ x = \"\\q\";\n'ab' @ 0b2 + \"abc");
+
+        assert_eq!(
+            tokens,
+            vec![
+                (Token::Let, 0..3),
+                (Token::Id("x".into()), 7..8),
+                (Token::Equals, 9..10),
+                (Token::Semicolon, 15..16),
+                (Token::Plus, 28..29)
+            ]
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    span: 4..6,
+                    error: LexError::StrayChars("$$".to_string())
+                },
+                Diagnostic {
+                    span: 11..15,
+                    error: LexError::InvalidEscape("\\q".to_string())
+                },
+                Diagnostic {
+                    span: 17..21,
+                    error: LexError::MultiChar
+                },
+                Diagnostic {
+                    span: 22..23,
+                    error: LexError::StrayChars("@".to_string())
+                },
+                Diagnostic {
+                    span: 24..27,
+                    error: LexError::InvalidDigit('2', 2)
+                },
+                Diagnostic {
+                    span: 30..34,
+                    error: LexError::UnterminatedStr("\"".to_string())
+                }
+            ]
+        );
+        assert_eq!(diagnostics[0].to_string(), "Unknown characters `$$`");
+        assert_eq!(launch("let x = 1;").1, vec![]);
+
+        let (tokens, diagnostics) = launch("let a = \"abc;\nlet b = 0b2;\nlet c = $;\n");
+
+        assert_eq!(tokens.len(), 11);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    span: 8..13,
+                    error: LexError::UnterminatedStr("\"".to_string())
+                },
+                Diagnostic {
+                    span: 22..25,
+                    error: LexError::InvalidDigit('2', 2)
+                },
+                Diagnostic {
+                    span: 35..36,
+                    error: LexError::StrayChars("$".to_string())
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn lossless_trivia() {
+        let source = "let x = 1; -- one\n\t/* two /* three */ */\n--- doc\nx\n";
+        let output = lossless(source);
+
+        assert_eq!(
+            output,
+            vec![
+                (Lossless::Token(Token::Let), 0..3),
+                (Lossless::Trivia(Trivia::Whitespace), 3..4),
+                (Lossless::Token(Token::Id("x".into())), 4..5),
+                (Lossless::Trivia(Trivia::Whitespace), 5..6),
+                (Lossless::Token(Token::Equals), 6..7),
+                (Lossless::Trivia(Trivia::Whitespace), 7..8),
+                (Lossless::Token(Token::Int(1)), 8..9),
+                (Lossless::Token(Token::Semicolon), 9..10),
+                (Lossless::Trivia(Trivia::Whitespace), 10..11),
+                (Lossless::Trivia(Trivia::LineComment), 11..17),
+                (Lossless::Trivia(Trivia::Whitespace), 17..19),
+                (Lossless::Trivia(Trivia::BlockComment), 19..40),
+                (Lossless::Trivia(Trivia::Whitespace), 40..41),
+                (Lossless::Token(Token::Doc("doc".into())), 41..48),
+                (Lossless::Trivia(Trivia::Whitespace), 48..49),
+                (Lossless::Token(Token::Id("x".into())), 49..50),
+                (Lossless::Trivia(Trivia::Whitespace), 50..51),
+            ]
+        );
+    }
+
+    #[test]
+    fn lossless_roundtrip() {
+        let sources = [
+            "",
+            "   ",
+            "-- only a comment",
+            "fun a(b) {\n    /* c */ return b; -- d\n}\n\n\n--- e\nclass F;",
+            "let x = \"unterminated /* -- \n",
+            "1 /* unterminated /* nested */",
+            "é ü ?? ~",
+        ];
+
+        for source in sources.iter() {
+            let output = lossless(source);
+            let mut end = 0;
+
+            for (_, span) in output.iter() {
+                assert_eq!(span.start, end);
+                end = span.end;
+            }
+
+            assert_eq!(end, source.len());
+            assert_eq!(
+                output
+                    .iter()
+                    .map(|(_, span)| &source[span.clone()])
+                    .collect::<String>(),
+                *source
+            );
+        }
+    }
+
     #[test]
     fn check_get_doc() {
         let mut lex = Token::lexer("--- hello\n---there\n---\n---  woo \n--- singleliner ---\n");
@@ -181,17 +1619,17 @@ mod tests {
     fn docs() {
         assert_eq!(
             Token::lexer("---hi there").next().unwrap(),
-            Token::Doc("hi there".to_string())
+            Token::Doc("hi there".into())
         );
         assert_eq!(
             Token::lexer("---     hi there     ").next().unwrap(),
-            Token::Doc("hi there".to_string())
+            Token::Doc("hi there".into())
         );
         assert_eq!(
             Token::lexer("---    hi there ---\n---   pretty cool eh?\n")
                 .next()
                 .unwrap(),
-            Token::Doc("hi there ---\npretty cool eh?".to_string())
+            Token::Doc("hi there ---\npretty cool eh?".into())
         );
     }
 }
@@ -1,6 +1,8 @@
 //! Frontend section, containing the frontend components of compilation such as
-//! lexing/scanning and parsing
+//! lexing/scanning, parsing, checking and documentation
 
 pub mod ast;
+pub mod check;
+pub mod doc;
 pub mod lexer;
 pub mod parser;
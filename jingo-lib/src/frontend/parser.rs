@@ -1,6 +1,10 @@
-use super::{ast::*, lexer::Token};
-use logos::Lexer;
-use std::fmt;
+use super::{
+    ast::*,
+    doc::Doc,
+    lexer::{Lex, LexError, StrPart, Token},
+};
+use logos::{Logos, Span};
+use std::{convert::TryFrom, fmt};
 
 /// Parsing-specific error/stop enumeration, encompassing the possible errors or
 /// stops in parsing flow which may have occurred during parsing
@@ -9,17 +13,100 @@ pub enum ParseStop {
     //
     // errors
     //
-    /// Unexpected token
-    UnexpectedToken,
+    /// Unexpected token was found in place of one of the `expected` tokens
+    UnexpectedToken {
+        /// Byte span of the token found
+        span: Span,
+
+        /// Token which was found
+        found: Token<'static>,
+
+        /// Source text of `found` as written rather than as decoded, such as
+        /// `0xFF` instead of `255`
+        text: String,
+
+        /// Tokens which would have been acceptable in place of `found`
+        expected: Vec<Expected>,
+
+        /// Source leading up to the token found, describing where it was found
+        after: Option<String>,
+    },
 
     /// Unknown token whilst lexing
-    UnknownToken,
+    UnknownToken {
+        /// Byte span of the unknown token
+        span: Span,
+
+        /// Source text of the unknown token
+        text: String,
+    },
+
+    /// Token was recognised whilst lexing but was malformed, such as a string
+    /// with an invalid escape sequence
+    InvalidToken {
+        /// Byte span of the invalid token
+        span: Span,
+
+        /// Reason the token was invalid
+        error: LexError,
+    },
 
     /// Operation was found with no lefthand expression
-    NoLeftExpr,
+    NoLeftExpr {
+        /// Byte span of the operation's token
+        span: Span,
+
+        /// Token of the operation found
+        found: Token<'static>,
+
+        /// Source text of `found`
+        text: String,
+    },
+
+    /// Two expressions were found without a `;` seperating them
+    NoSeparator {
+        /// Byte span of the token found in place of the `;`
+        span: Span,
+
+        /// Token which was found in place of the `;`
+        found: Token<'static>,
+
+        /// Source text of `found` as written rather than as decoded
+        text: String,
+
+        /// Source leading up to the token found, describing where it was found
+        after: Option<String>,
+    },
+
+    /// Statement ending in a `}` enclosed body was followed by a binary
+    /// operation, which would only apply to it if it was wrapped in `( )`
+    BodyOperand {
+        /// Byte span of the operation's token
+        span: Span,
+
+        /// Token of the operation found
+        found: Token<'static>,
+
+        /// Source text of `found`
+        text: String,
 
-    /// File ended unexpectedly
-    UnexpectedEof,
+        /// Source of the statement before the operation, cut short to its first
+        /// line if it spans several
+        after: Option<String>,
+    },
+
+    /// File ended unexpectedly where one of the `expected` tokens should have
+    /// been
+    UnexpectedEof {
+        /// Byte span of the end of the file
+        span: Span,
+
+        /// Tokens which would have been acceptable in place of the file ending
+        expected: Vec<Expected>,
+
+        /// Source leading up to the end of file, describing where it ended
+        after: Option<String>,
+    },
 
     //
     // special
@@ -28,11 +115,19 @@ pub enum ParseStop {
     FileEnded,
 }
 
-impl<T> From<Option<T>> for ParseStop {
-    fn from(option: Option<T>) -> ParseStop {
-        match option {
-            Some(_) => ParseStop::UnexpectedToken,
-            None => ParseStop::UnexpectedEof,
+impl ParseStop {
+    /// Gets the byte span this error occurred at, returning [None] for the
+    /// special [ParseStop::FileEnded] stop
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParseStop::UnexpectedToken { span, .. }
+            | ParseStop::UnknownToken { span, .. }
+            | ParseStop::InvalidToken { span, .. }
+            | ParseStop::NoLeftExpr { span, .. }
+            | ParseStop::NoSeparator { span, .. }
+            | ParseStop::BodyOperand { span, .. }
+            | ParseStop::UnexpectedEof { span, .. } => Some(span.clone()),
+            ParseStop::FileEnded => None,
         }
     }
 }
@@ -40,12 +135,42 @@ impl<T> From<Option<T>> for ParseStop {
 impl fmt::Display for ParseStop {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ParseStop::UnexpectedToken => write!(f, "Unexpected token"),
-            ParseStop::UnknownToken => write!(f, "Unknown token"),
-            ParseStop::NoLeftExpr => {
-                write!(f, "Operation was found with no lefthand expression")
+            ParseStop::UnexpectedToken {
+                text,
+                expected,
+                after,
+                ..
+            } => write!(
+                f,
+                "expected {}{}, found `{}`",
+                Expected::list(expected),
+                fmt_after(after),
+                text
+            ),
+            ParseStop::UnknownToken { text, .. } => write!(f, "Unknown token `{}`", text),
+            ParseStop::InvalidToken { error, .. } => write!(f, "{}", error),
+            ParseStop::NoLeftExpr { text, .. } => write!(
+                f,
+                "Operation `{}` was found with no lefthand expression",
+                text
+            ),
+            ParseStop::NoSeparator { text, after, .. } => {
+                write!(f, "expected `;`{}, found `{}`", fmt_after(after), text)
             }
-            ParseStop::UnexpectedEof => write!(f, "File ended unexpectedly"),
+            ParseStop::BodyOperand { text, after, .. } => write!(
+                f,
+                "expected `;`{}, found `{}`, wrap it in `( )` to use it as an operand",
+                fmt_after(after),
+                text
+            ),
+            ParseStop::UnexpectedEof {
+                expected, after, ..
+            } => write!(
+                f,
+                "expected {}{}, found end of file",
+                Expected::list(expected),
+                fmt_after(after)
+            ),
             ParseStop::FileEnded => {
                 write!(f, "File ended expectedly, please report this as a bug!")
             }
@@ -53,221 +178,2356 @@ impl fmt::Display for ParseStop {
     }
 }
 
+/// Gets the source text leading up to and including the last consumed token
+/// of a given `lex`, starting from the beginning of its line or the last
+/// `;`, `{` or `}` before it, in order to describe where errors occurred
+fn after(lex: &Tokens) -> Option<String> {
+    let span = lex.span();
+    let source = lex.source();
+    let line_start = source[..span.start].rfind('\n').map_or(0, |ind| ind + 1);
+
+    let mut line = Token::lexer(&source[line_start..span.start]);
+    let mut start = line_start;
+
+    while let Some(token) = line.next() {
+        if matches!(
+            token,
+            Token::Semicolon | Token::BraceLeft | Token::BraceRight
+        ) {
+            start = line_start + line.span().end;
+        }
+    }
+
+    match source[start..span.end].trim() {
+        "" => None,
+        after => Some(after.to_string()),
+    }
+}
+
+/// Formats the optional `after` source of a [ParseStop]
+fn fmt_after(after: &Option<String>) -> String {
+    match after {
+        Some(after) => format!(" after `{}`", after),
+        None => String::new(),
+    }
+}
+
+/// Something which would have been acceptable in place of an unexpected token
+/// or end of file, used inside of [ParseStop]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    /// A specific token
+    Token(Token<'static>),
+
+    /// Any [Token::Id]
+    Id,
+
+    /// The start of any expression
+    Expr,
+}
+
+impl Expected {
+    /// Formats a list of expected values in a human-readable manner, i.e.
+    /// `` `,` or `)` ``
+    fn list(expected: &[Expected]) -> String {
+        let mut items: Vec<String> = expected.iter().map(|item| item.to_string()).collect();
+
+        match items.pop() {
+            Some(last) if items.is_empty() => last,
+            Some(last) => format!("{} or {}", items.join(", "), last),
+            None => "nothing".to_string(),
+        }
+    }
+}
+
+impl From<Token<'static>> for Expected {
+    fn from(token: Token<'static>) -> Self {
+        Expected::Token(token)
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Token(token) => write!(f, "`{}`", token),
+            Expected::Id => write!(f, "an identifier"),
+            Expected::Expr => write!(f, "an expression"),
+        }
+    }
+}
+
+/// Lexed token along with its byte span and the reason it's a [Token::Error]
+/// if it is one
+type Lexed<'src> = (Option<Token<'src>>, Span, Option<LexError>);
+
+/// Lexer wrapper buffering a single token of lookahead, so that peeking at the
+/// next token several times before consuming it only lexes it once
+struct Tokens<'src> {
+    /// Underlying lexer, which is one token ahead if `peeked` is filled
+    lex: Lex<'src>,
+
+    /// Next token if it has been peeked at but not yet consumed
+    peeked: Option<Lexed<'src>>,
+
+    /// Byte span of the last consumed token
+    span: Span,
+}
+
+impl<'src> Tokens<'src> {
+    /// Creates a new lexer over `source` which starts at the `start` byte
+    fn at(source: &'src str, start: usize) -> Self {
+        let mut lex = Token::lexer(source);
+        lex.bump(start);
+
+        Self {
+            lex,
+            peeked: None,
+            span: start..start,
+        }
+    }
+
+    /// Consumes and returns the next token
+    fn next(&mut self) -> Option<Token<'src>> {
+        let (token, span, _) = match self.peeked.take() {
+            Some(lexed) => lexed,
+            None => self.lex_next(),
+        };
+
+        self.span = span;
+        token
+    }
+
+    /// Peeks at the next token without consuming it
+    fn peek(&mut self) -> Option<&Token<'src>> {
+        self.fill().0.as_ref()
+    }
+
+    /// Gets the byte span of the next token without consuming it, which is an
+    /// empty span at the end of the source if there are no more tokens
+    fn peek_span(&mut self) -> Span {
+        self.fill().1.clone()
+    }
+
+    /// Gets the reason the next token is a [Token::Error] without consuming it
+    fn peek_error(&mut self) -> Option<LexError> {
+        self.fill().2.clone()
+    }
+
+    /// Byte span of the last consumed token
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    /// Entire source being lexed
+    fn source(&self) -> &'src str {
+        self.lex.source()
+    }
+
+    /// Source text of the last consumed token
+    fn slice(&self) -> &'src str {
+        &self.source()[self.span()]
+    }
+
+    /// Lexes the next token into the lookahead buffer if it's empty
+    fn fill(&mut self) -> &Lexed<'src> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.lex_next());
+        }
+
+        self.peeked.as_ref().unwrap()
+    }
+
+    /// Lexes the next token from the underlying lexer, ignoring the buffer
+    fn lex_next(&mut self) -> Lexed<'src> {
+        let token = self.lex.next();
+        let error = self.lex.extras.take();
+
+        match token {
+            Some(Token::Error) => (token, self.lex.span(), error),
+            Some(_) => (token, self.lex.span(), None),
+            None => {
+                let len = self.source().len();
+                (None, len..len, None)
+            }
+        }
+    }
+}
+
 /// Parses a given lexer input into the resulting parsed values
-pub fn launch(lex: &mut Lexer<Token>) -> Result<Vec<Expr>, ParseStop> {
-    let mut buf = None;
+pub fn launch(lex: &mut Lex) -> Result<Vec<Expr>, ParseStop> {
+    let lex = &mut Tokens::at(lex.source(), lex.span().end);
+    let mut output = vec![];
+
+    loop {
+        match statement(lex, true) {
+            Ok(expr) => {
+                separator(lex, &expr, None)?;
+                output.push(expr)
+            }
+            Err(ParseStop::FileEnded) => break,
+            Err(unknown) => return Err(unknown),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Parses a given lexer input similarly to [launch], but recovers from any
+/// errors found in order to report every error in the input, returning all
+/// expressions which could still be parsed
+pub fn launch_recover(lex: &mut Lex) -> (Vec<Expr>, Vec<ParseStop>) {
+    let lex = &mut Tokens::at(lex.source(), lex.span().end);
     let mut output = vec![];
+    let mut errors = vec![];
 
     loop {
-        let buf_was_some = buf.is_some();
+        let start = lex.span().end;
 
-        match next(lex, &mut buf, None, true) {
+        let err = match statement(lex, true) {
             Ok(expr) => {
-                if buf_was_some && buf.is_some() {
-                    output.push(buf.take().unwrap());
-                }
+                let sep = separator(lex, &expr, None);
+                output.push(expr);
 
-                buf = Some(expr);
+                match sep {
+                    Ok(()) => continue,
+                    Err(err) => err,
+                }
             }
             Err(ParseStop::FileEnded) => break,
-            Err(unknown) => return Err(unknown.into()),
+            Err(err) => err,
+        };
+
+        errors.push(err);
+
+        // find how deep into bodies the error occurred so they may be exited
+        let depth =
+            Token::lexer(&lex.source()[start..lex.span().end]).fold(0, |depth: usize, token| {
+                match token {
+                    Token::BraceLeft => depth + 1,
+                    Token::BraceRight => depth.saturating_sub(1),
+                    _ => depth,
+                }
+            });
+
+        synchronise(lex, depth);
+    }
+
+    (output, errors)
+}
+
+/// Skips over tokens until parsing may safely continue after an error, being
+/// just after a `;` or the `}` ending the body the error was found in along
+/// with any `;` following it, or just before a `fun`, `class` or `let` item
+/// keyword outside of any bodies
+fn synchronise(lex: &mut Tokens, mut depth: usize) {
+    loop {
+        match lex.peek() {
+            None => return,
+            Some(Token::Fun) | Some(Token::Class) | Some(Token::Let) if depth == 0 => return,
+            Some(Token::Semicolon) if depth == 0 => {
+                lex.next();
+                return;
+            }
+            Some(Token::BraceLeft) => depth += 1,
+            Some(Token::BraceRight) if depth <= 1 => {
+                lex.next();
+
+                match lex.peek() {
+                    // continue through any `else` following a partial `if`
+                    Some(Token::Else) => depth = 0,
+                    Some(Token::Semicolon) => {
+                        lex.next();
+                        return;
+                    }
+                    _ => return,
+                }
+            }
+            Some(Token::BraceRight) => depth -= 1,
+            Some(_) => (),
         }
+
+        lex.next();
     }
+}
+
+/// Gets the next full expression including any binary operations, used
+/// internally as the main parsing hook
+fn next(lex: &mut Tokens, is_topmost: bool) -> Result<Expr, ParseStop> {
+    let doc = leading_doc(lex);
+    let left = single(lex, is_topmost)?;
+    let mut expr = op_flow(lex, left, 0)?;
 
-    match buf {
-        Some(expr) => output.push(expr),
-        None => (),
+    if doc.is_some() {
+        expr.doc = doc;
     }
 
-    Ok(output)
+    Ok(expr)
+}
+
+/// Gets the next statement of a body or file, similar to [next] but without
+/// continuing into binary operations after items ending in a `}` enclosed body,
+/// as these may leave out their `;` so that `fun f() {}\n-x;` is two statements
+fn statement(lex: &mut Tokens, is_topmost: bool) -> Result<Expr, ParseStop> {
+    let doc = leading_doc(lex);
+    let left = single(lex, is_topmost)?;
+    let mut expr = if ends_in_body(&left.kind) {
+        match lex.peek() {
+            // `-` may start the next statement instead, as in `fun f() {}\n-x;`
+            Some(token) if token != &Token::Minus && op_info(token).is_some() => {
+                let found = token.clone().into_owned();
+                let span = lex.peek_span();
+                let source = &lex.source()[left.range.clone()];
+
+                return Err(ParseStop::BodyOperand {
+                    text: lex.source()[span.clone()].to_string(),
+                    found,
+                    span,
+                    after: Some(match source.find('\n') {
+                        Some(ind) => format!("{} …", source[..ind].trim_end()),
+                        None => source.to_string(),
+                    }),
+                });
+            }
+            _ => left,
+        }
+    } else {
+        op_flow(lex, left, 0)?
+    };
+
+    if doc.is_some() {
+        expr.doc = doc;
+    }
+
+    Ok(expr)
+}
+
+/// Consumes a doc comment if one is next, which documents the whole expression
+/// following it rather than just its left operand
+fn leading_doc(lex: &mut Tokens) -> Option<Doc> {
+    match lex.peek() {
+        Some(Token::Doc(string)) => {
+            let doc = Doc::from(&**string);
+            lex.next();
+            Some(doc)
+        }
+        _ => None,
+    }
 }
 
-/// Gets the next full expression, used internally as the main parsing hook
-fn next(
-    lex: &mut Lexer<Token>,
-    buf: &mut Option<Expr>,
-    doc: Option<String>,
-    is_topmost: bool,
-) -> Result<Expr, ParseStop> {
+/// Gets the next single expression without consuming any binary operations
+/// which may follow it
+fn single(lex: &mut Tokens, is_topmost: bool) -> Result<Expr, ParseStop> {
+    let prev = lex.span();
     let cur = lex.next();
     let start = lex.span().start;
 
-    match cur {
-        Some(Token::Plus) => Ok(Expr::from_parse(
-            op_flow(lex, buf, OpKind::Add)?,
-            None,
-            start,
-        )),
-        Some(Token::FwdSlash) => Ok(Expr::from_parse(
-            op_flow(lex, buf, OpKind::Div)?,
-            None,
-            start,
-        )),
-        Some(Token::Exclaim) => Ok(Expr::from_parse(Not(box_next(lex)?), doc, start)),
-        Some(Token::True) => Ok(Expr::from_parse(BoolLit(true), doc, start)),
-        Some(Token::False) => Ok(Expr::from_parse(BoolLit(false), doc, start)),
-        Some(Token::Let) => Ok(Expr::from_parse(let_flow(lex)?, doc, start)),
-        Some(Token::Return) => Ok(Expr::from_parse(Return(box_next(lex)?), doc, start)),
-        Some(Token::Str(d)) => Ok(Expr::from_parse(StrLit(d), doc, start)),
-        Some(Token::Char(d)) => Ok(Expr::from_parse(CharLit(d), doc, start)),
-        Some(Token::Float(d)) => Ok(Expr::from_parse(FloatLit(d), doc, start)),
-        Some(Token::Int(d)) => Ok(Expr::from_parse(IntLit(d), doc, start)),
-        Some(Token::Id(id)) => Ok(Expr::from_parse(path_flow(lex, vec![id])?, doc, start)),
-        Some(Token::Doc(string)) => next(lex, buf, Some(string), is_topmost),
-        Some(Token::Error) => Err(ParseStop::UnknownToken),
-        Some(_) => Err(ParseStop::UnexpectedToken),
-        None => Err(match is_topmost {
-            true => ParseStop::FileEnded,
-            false => ParseStop::UnexpectedEof,
-        }),
-    }
-}
-
-/// Path flow for all [Token::Path] or [Token::Id]
-fn path_flow(_lex: &mut Lexer<Token>, _path: Vec<String>) -> Result<ExprKind, ParseStop> {
-    todo!("path/id flow")
-}
-
-/// Flow for operation grammar, i.e. adding or subtracting
-fn op_flow(lex: &mut Lexer<Token>, buf: &mut Option<Expr>, kind: OpKind) -> Result<Op, ParseStop> {
-    Ok(Op {
-        left: Box::new(buf.take().ok_or(ParseStop::NoLeftExpr)?),
-        right: box_next(lex)?,
-        kind,
+    let kind: ExprKind = match cur {
+        Some(Token::Exclaim) => Not(box_single(lex)?).into(),
+        Some(Token::Minus) => neg_flow(lex, start)?,
+        Some(Token::Tilde) => BitNot(box_single(lex)?).into(),
+        Some(Token::BraceLeft) => Block(block_flow(lex)?).into(),
+        Some(Token::ParenLeft) => {
+            let mut inner = next(lex, false)?;
+            ensure(lex, Token::ParenRight)?;
+            inner.range = start..lex.span().end;
+
+            return postfix_flow(lex, inner);
+        }
+        Some(Token::True) => BoolLit(true).into(),
+        Some(Token::False) => BoolLit(false).into(),
+        Some(Token::None) => NoneLit.into(),
+        Some(Token::This) => This.into(),
+        Some(Token::Let) => let_flow(lex)?.into(),
+        Some(Token::Fun) => fun_flow(lex)?,
+        Some(Token::Class) => class_flow(lex)?.into(),
+        Some(Token::If) => if_flow(lex)?.into(),
+        Some(Token::While) => While {
+            condition: box_next(lex)?,
+            body: body_flow(lex)?,
+        }
+        .into(),
+        Some(Token::Loop) => Loop {
+            body: body_flow(lex)?,
+        }
+        .into(),
+        Some(Token::Break) => break_flow(lex)?.into(),
+        Some(Token::Continue) => Continue.into(),
+        Some(Token::Return) => Return(box_next(lex)?).into(),
+        Some(Token::Str(d)) => StrLit(d.into_owned()).into(),
+        Some(Token::InterpStr(parts)) => interp_flow(lex, parts)?.into(),
+        Some(Token::Char(d)) => CharLit(d).into(),
+        Some(Token::Float(d)) => FloatLit(d).into(),
+        Some(Token::Int(d)) => IntLit(int_lit(d, false, lex.span())?).into(),
+        Some(Token::Id(name)) => {
+            let range = lex.span();
+            path_flow(
+                lex,
+                vec![Id {
+                    name: name.into_owned(),
+                    range,
+                }],
+            )?
+        }
+        Some(Token::Path(path)) => {
+            let path = path_ids(&path, lex);
+            path_flow(lex, path)?
+        }
+        Some(Token::Doc(string)) => {
+            let mut expr = single(lex, is_topmost)?;
+            expr.doc = Some(Doc::from(&*string));
+            return Ok(expr);
+        }
+        Some(token) if op_info(&token).is_some() => {
+            return Err(ParseStop::NoLeftExpr {
+                span: lex.span(),
+                found: token.into_owned(),
+                text: lex.slice().to_string(),
+            })
+        }
+        None if is_topmost => return Err(ParseStop::FileEnded),
+        _ => {
+            return Err(unexpected(
+                &mut rewind(lex.source(), prev),
+                vec![Expected::Expr],
+            ))
+        }
+    };
+
+    let expr = Expr::from_parse(kind, None, start..lex.span().end);
+
+    match expr.kind {
+        ExprKind::Path(_) | ExprKind::FunctionCall(_) | ExprKind::This(_) => {
+            postfix_flow(lex, expr)
+        }
+        _ => Ok(expr),
+    }
+}
+
+/// Flow for a `-` prefix, expecting it to have already been consumed. Negative
+/// number literals are folded into a single literal so that [i64::MIN] may be
+/// written, unless an exponent binds tighter as in `-2 ** 2`
+fn neg_flow(lex: &mut Tokens, start: usize) -> Result<ExprKind, ParseStop> {
+    let literal = match lex.peek() {
+        Some(Token::Int(_)) | Some(Token::Float(_)) => lex.next(),
+        _ => return Ok(Neg(box_single(lex)?).into()),
+    };
+
+    if lex.peek() == Some(&Token::StarStar) {
+        let kind: ExprKind = match literal {
+            Some(Token::Int(d)) => IntLit(int_lit(d, false, lex.span())?).into(),
+            Some(Token::Float(d)) => FloatLit(d).into(),
+            _ => unreachable!(),
+        };
+        let operand = Expr::from_parse(kind, None, lex.span());
+
+        return Ok(Neg(Box::new(op_flow(lex, operand, POW_PREC)?)).into());
+    }
+
+    Ok(match literal {
+        Some(Token::Int(d)) => IntLit(int_lit(d, true, start..lex.span().end)?).into(),
+        Some(Token::Float(d)) => FloatLit(-d).into(),
+        _ => unreachable!(),
     })
 }
 
-/// Flow for `let` grammar
-fn let_flow(lex: &mut Lexer<Token>) -> Result<Let, ParseStop> {
-    match lex.next() {
-        Some(Token::Mut) => Ok(Let {
-            mutable: true,
-            id: get_id(lex)?,
-            expr: {
-                ensure(lex, Token::Equals)?;
-                box_next(lex)?
-            },
-        }),
-        Some(Token::Id(id)) => Ok(Let {
-            mutable: false,
-            id: id.into(),
-            expr: {
-                ensure(lex, Token::Equals)?;
-                box_next(lex)?
-            },
-        }),
-        unknown => Err(unknown.into()),
+/// Range-checks the magnitude of an integer literal lexed at `span`, being
+/// `negative` if a `-` was folded into it
+fn int_lit(magnitude: u64, negative: bool, span: Span) -> Result<i64, ParseStop> {
+    let value = if negative {
+        -i128::from(magnitude)
+    } else {
+        i128::from(magnitude)
+    };
+
+    i64::try_from(value).map_err(|_| ParseStop::InvalidToken {
+        span,
+        error: LexError::IntTooLarge,
+    })
+}
+
+/// Path flow for all [Token::Path] or [Token::Id], branching into calls and
+/// setters depending on the tokens which follow
+fn path_flow(lex: &mut Tokens, path: Vec<Id>) -> Result<ExprKind, ParseStop> {
+    let mut path = Path(path);
+
+    match lex.peek() {
+        Some(Token::ParenLeft) => {
+            lex.next();
+
+            // paths always contain at least one id
+            Ok(FunctionCall {
+                id: path.last().unwrap(),
+                path,
+                args: args_flow(lex)?,
+            }
+            .into())
+        }
+        Some(token) if set_kind(token).is_some() => {
+            let kind = set_kind(token).unwrap();
+            set_flow(lex, None, path, kind)
+        }
+        _ => Ok(path.into()),
     }
 }
 
-/// Gets id from next [Lexer] token or errors
-fn get_id(lex: &mut Lexer<Token>) -> Result<Id, ParseStop> {
-    match lex.next() {
-        Some(Token::Id(id)) => Ok(id.into()),
-        unknown => Err(unknown.into()),
+/// Flow for any `.field` getters, `.method()` calls or `.field = x` setters
+/// chained onto the end of `expr`, such as `SomeClass::new(3).multiply(2)`
+fn postfix_flow(lex: &mut Tokens, mut expr: Expr) -> Result<Expr, ParseStop> {
+    while lex.peek() == Some(&Token::Dot) {
+        lex.next();
+
+        let id = get_id(lex)?;
+        let start = expr.range.start;
+        let receiver = Box::new(expr);
+
+        let kind: ExprKind = match lex.peek() {
+            Some(Token::ParenLeft) => {
+                lex.next();
+
+                MethodCall {
+                    receiver,
+                    id,
+                    args: args_flow(lex)?,
+                }
+                .into()
+            }
+            Some(token) if set_kind(token).is_some() => {
+                // setters take the rest of the expression so end the chain
+                let kind = set_kind(token).unwrap();
+                let kind = set_flow(lex, Some(receiver), Path(vec![id]), kind)?;
+                return Ok(Expr::from_parse(kind, None, start..lex.span().end));
+            }
+            _ => Field { receiver, id }.into(),
+        };
+
+        expr = Expr::from_parse(kind, None, start..lex.span().end);
     }
+
+    Ok(expr)
 }
 
-/// Gets next expression without passing a previous `buf` of `doc` and returns a
-/// [Box], used as a shortcut for sequential parsing
-fn box_next(lex: &mut Lexer<Token>) -> Result<Box<Expr>, ParseStop> {
-    Ok(Box::new(next(lex, &mut None, None, false)?))
+/// Flow for setters, expecting the next token to be the `=` or compound
+/// assignment of the given `kind` following the target of the setter
+fn set_flow(
+    lex: &mut Tokens,
+    receiver: Option<Box<Expr>>,
+    mut path: Path,
+    kind: Option<OpKind>,
+) -> Result<ExprKind, ParseStop> {
+    lex.next();
+
+    // targets always contain at least one id
+    let id = path.last().unwrap();
+    let expr = box_next(lex)?;
+
+    Ok(match kind {
+        Some(kind) => OpSetLet {
+            receiver,
+            id,
+            path,
+            kind,
+            expr,
+        }
+        .into(),
+        None => SetLet {
+            receiver,
+            id,
+            path,
+            expr,
+        }
+        .into(),
+    })
 }
 
-/// Ensures next lex token equals inputted `token` value
-fn ensure(lex: &mut Lexer<Token>, token: Token) -> Result<(), ParseStop> {
-    let next = lex.next();
+/// Flow for arguments passed to calls, expecting the opening `(` to have already
+/// been consumed
+fn args_flow(lex: &mut Tokens) -> Result<Vec<Expr>, ParseStop> {
+    let mut args = vec![];
 
-    if next == Some(token) {
-        Ok(())
-    } else {
-        Err(next.into())
+    if lex.peek() == Some(&Token::ParenRight) {
+        lex.next();
+        return Ok(args);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use logos::Logos;
+    loop {
+        args.push(next(lex, false)?);
 
-    #[test]
-    fn lets() {
-        assert_eq!(
-            next(&mut Token::lexer("let x = 5"), &mut None, None, true).unwrap(),
-            Expr {
-                kind: ExprKind::Let(Let {
-                    mutable: false,
-                    id: Id("x".to_string()),
-                    expr: Box::new(Expr {
-                        kind: ExprKind::IntLit(IntLit(5)),
-                        doc: None,
-                        start: 8
-                    })
-                }),
-                doc: None,
-                start: 0
+        match lex.peek() {
+            Some(Token::Comma) => {
+                lex.next();
+
+                if lex.peek() == Some(&Token::ParenRight) {
+                    lex.next();
+                    return Ok(args);
+                }
             }
-        );
+            Some(Token::ParenRight) => {
+                lex.next();
+                return Ok(args);
+            }
+            _ => {
+                return Err(unexpected(
+                    lex,
+                    vec![Token::Comma.into(), Token::ParenRight.into()],
+                ))
+            }
+        }
     }
+}
 
-    #[test]
-    fn basic_errs() {
-        assert_eq!(
-            next(&mut Token::lexer("let x + 5"), &mut None, None, true),
-            Err(ParseStop::UnexpectedToken)
-        );
-        assert_eq!(
-            next(&mut Token::lexer("#"), &mut None, None, true),
-            Err(ParseStop::UnknownToken)
-        );
-        assert_eq!(
-            next(&mut Token::lexer("let x = -- 5"), &mut None, None, true),
-            Err(ParseStop::UnexpectedEof)
+/// Flow for operation grammar, i.e. adding or subtracting, using precedence
+/// climbing to fold any binary operations following `left` which bind at least
+/// as tightly as `min_prec`
+fn op_flow(lex: &mut Tokens, mut left: Expr, min_prec: u8) -> Result<Expr, ParseStop> {
+    loop {
+        let (kind, prec) = match lex.peek().and_then(op_info) {
+            Some((kind, prec)) if prec >= min_prec => (kind, prec),
+            _ => return Ok(left),
+        };
+
+        lex.next();
+
+        // left-associative operations may only contain operations binding
+        // tighter than this one on their righthand side, whereas exponents are
+        // right-associative so `2 ** 3 ** 2` is `2 ** (3 ** 2)`
+        let next_prec = if kind == OpKind::Pow { prec } else { prec + 1 };
+        let right = single(lex, false)?;
+        let right = op_flow(lex, right, next_prec)?;
+        let range = left.range.start..right.range.end;
+
+        left = Expr::from_parse(
+            Op {
+                left: Box::new(left),
+                right: Box::new(right),
+                kind,
+            },
+            None,
+            range,
         );
     }
+}
 
-    #[test]
-    fn parse_launch() {
-        assert_eq!(
-            launch(&mut Token::lexer("5 + 3")).unwrap(),
-            vec![Expr {
-                kind: ExprKind::Op(Op {
-                    left: Box::new(Expr {
-                        kind: ExprKind::IntLit(IntLit(5)),
-                        doc: None,
-                        start: 0
-                    }),
-                    right: Box::new(Expr {
-                        kind: ExprKind::IntLit(IntLit(3)),
-                        doc: None,
-                        start: 4
-                    }),
-                    kind: OpKind::Add
-                }),
-                doc: None,
-                start: 2
-            }]
-        );
-        assert_eq!(
-            launch(&mut Token::lexer("!5")).unwrap(),
-            vec![Expr {
-                kind: ExprKind::Not(Not(Box::new(Expr {
-                    kind: ExprKind::IntLit(IntLit(5)),
-                    doc: None,
-                    start: 1
-                }))),
-                doc: None,
-                start: 0
-            }]
-        );
-        assert_eq!(launch(&mut Token::lexer("+ 5")), Err(ParseStop::NoLeftExpr));
+/// Precedence of exponents, the tightest binding binary operation which is
+/// also the only one binding tighter than prefix operators so `-2 ** 2` is
+/// `-(2 ** 2)`
+const POW_PREC: u8 = 11;
+
+/// Gets the operation kind and precedence of a binary operator token, with
+/// higher precedences binding tighter
+fn op_info(token: &Token) -> Option<(OpKind, u8)> {
+    Some(match token {
+        Token::Or => (OpKind::Or, 1),
+        Token::And => (OpKind::And, 2),
+        Token::EqualsEquals => (OpKind::EqEq, 3),
+        Token::ExclaimEquals => (OpKind::NotEq, 3),
+        Token::Greater => (OpKind::Greater, 4),
+        Token::GreaterEquals => (OpKind::GreaterEq, 4),
+        Token::Less => (OpKind::Less, 4),
+        Token::LessEquals => (OpKind::LessEq, 4),
+        Token::Pipe => (OpKind::BitOr, 5),
+        Token::Caret => (OpKind::BitXor, 6),
+        Token::Ampersand => (OpKind::BitAnd, 7),
+        Token::LessLess => (OpKind::Shl, 8),
+        Token::GreaterGreater => (OpKind::Shr, 8),
+        Token::Plus => (OpKind::Add, 9),
+        Token::Minus => (OpKind::Sub, 9),
+        Token::Star => (OpKind::Mul, 10),
+        Token::FwdSlash => (OpKind::Div, 10),
+        Token::Percent => (OpKind::Mod, 10),
+        Token::StarStar => (OpKind::Pow, POW_PREC),
+        _ => return None,
+    })
+}
+
+/// Gets the kind of an assignment token, being [None] inside for a plain `=`
+/// or the operation kind of a compound assignment such as `+=`
+fn set_kind(token: &Token) -> Option<Option<OpKind>> {
+    Some(match token {
+        Token::Equals => None,
+        Token::PlusEquals => Some(OpKind::PlusEq),
+        Token::MinusEquals => Some(OpKind::SubEq),
+        Token::StarEquals => Some(OpKind::MulEq),
+        Token::FwdSlashEquals => Some(OpKind::DivEq),
+        _ => return None,
+    })
+}
+
+/// Flow for interpolated strings, parsing the source of each embedded
+/// expression with its own lexer so their ranges stay relative to the file
+fn interp_flow(lex: &mut Tokens, parts: Vec<StrPart>) -> Result<InterpStr, ParseStop> {
+    let mut output = vec![];
+
+    for part in parts {
+        output.push(match part {
+            StrPart::Lit(lit) => InterpPart::Lit(lit.into_owned()),
+            StrPart::Expr(span) => {
+                // includes the closing `}` so errors may point to it
+                let mut inner = Tokens::at(&lex.source()[..span.end + 1], span.start);
+
+                let expr = next(&mut inner, false)?;
+                ensure(&mut inner, Token::BraceRight)?;
+
+                InterpPart::Expr(Box::new(expr))
+            }
+        })
+    }
+
+    Ok(InterpStr(output))
+}
+
+/// Flow for `let` grammar
+fn let_flow(lex: &mut Tokens) -> Result<Let, ParseStop> {
+    let mutable = match lex.peek() {
+        Some(Token::Mut) => {
+            lex.next();
+            true
+        }
+        Some(Token::Id(_)) => false,
+        _ => return Err(unexpected(lex, vec![Token::Mut.into(), Expected::Id])),
+    };
+
+    Ok(Let {
+        mutable,
+        id: get_id(lex)?,
+        expr: {
+            ensure(lex, Token::Equals)?;
+            box_next(lex)?
+        },
+    })
+}
+
+/// Flow for `class` grammar, either being a bodiless `class Name;` or with a
+/// `{}` enclosed body of `,` seperated fields. The `;` of a bodiless class is
+/// left for [separator] to consume
+fn class_flow(lex: &mut Tokens) -> Result<Class, ParseStop> {
+    let id = get_id(lex)?;
+    let mut fields = vec![];
+
+    match lex.peek() {
+        Some(Token::Semicolon) => return Ok(Class { id, fields }),
+        Some(Token::BraceLeft) => lex.next(),
+        _ => {
+            return Err(unexpected(
+                lex,
+                vec![Token::Semicolon.into(), Token::BraceLeft.into()],
+            ))
+        }
+    };
+
+    loop {
+        let doc = match lex.peek() {
+            Some(Token::BraceRight) => {
+                lex.next();
+                return Ok(Class { id, fields });
+            }
+            Some(Token::Doc(_)) => leading_doc(lex),
+            _ => None,
+        };
+
+        let field_id = get_id(lex)?;
+        let default = match lex.peek() {
+            Some(Token::Equals) => {
+                lex.next();
+                Some(next(lex, false)?)
+            }
+            _ => None,
+        };
+
+        fields.push(ClassField {
+            id: field_id,
+            default,
+            doc,
+        });
+
+        match lex.peek() {
+            Some(Token::Comma) => lex.next(),
+            Some(Token::BraceRight) => {
+                lex.next();
+                return Ok(Class { id, fields });
+            }
+            _ => {
+                return Err(unexpected(
+                    lex,
+                    vec![
+                        Token::Equals.into(),
+                        Token::Comma.into(),
+                        Token::BraceRight.into(),
+                    ],
+                ))
+            }
+        };
+    }
+}
+
+/// Flow for `fun` grammar, branching into a [Function] for `fun name()`, a
+/// creation [Method] for `fun Class::name()` or a normal [Method] for
+/// `fun Class.name()`
+fn fun_flow(lex: &mut Tokens) -> Result<ExprKind, ParseStop> {
+    match lex.peek() {
+        Some(Token::Id(_)) => {
+            let id = get_id(lex)?;
+
+            if lex.peek() == Some(&Token::Dot) {
+                lex.next();
+
+                Ok(Method {
+                    class_id: id,
+                    creation_method: false,
+                    id: get_id(lex)?,
+                    args: params_flow(lex)?,
+                    body: body_flow(lex)?,
+                }
+                .into())
+            } else {
+                Ok(Function {
+                    id,
+                    args: params_flow(lex)?,
+                    body: body_flow(lex)?,
+                }
+                .into())
+            }
+        }
+        Some(Token::Path(path)) if path.split("::").count() == 2 => {
+            let path = path.to_string();
+            lex.next();
+
+            let (id, class_id) = Path(path_ids(&path, lex)).last_2().unwrap();
+
+            Ok(Method {
+                class_id,
+                creation_method: true,
+                id,
+                args: params_flow(lex)?,
+                body: body_flow(lex)?,
+            }
+            .into())
+        }
+        _ => Err(unexpected(lex, vec![Expected::Id])),
+    }
+}
+
+/// Flow for `()` enclosed parameters of a [Function] or [Method] definition,
+/// each being an [Id] seperated by a `,`
+fn params_flow(lex: &mut Tokens) -> Result<Vec<Id>, ParseStop> {
+    ensure(lex, Token::ParenLeft)?;
+
+    let mut params = vec![];
+
+    loop {
+        match lex.peek() {
+            Some(Token::ParenRight) => {
+                lex.next();
+                return Ok(params);
+            }
+            Some(Token::Id(_)) => params.push(get_id(lex)?),
+            _ => {
+                return Err(unexpected(
+                    lex,
+                    vec![Expected::Id, Token::ParenRight.into()],
+                ))
+            }
+        }
+
+        match lex.peek() {
+            Some(Token::Comma) => lex.next(),
+            Some(Token::ParenRight) => {
+                lex.next();
+                return Ok(params);
+            }
+            _ => {
+                return Err(unexpected(
+                    lex,
+                    vec![Token::Comma.into(), Token::ParenRight.into()],
+                ))
+            }
+        };
+    }
+}
+
+/// Flow for `if` grammar, including any `else if` or `else` segments which
+/// follow, expecting the opening `if` to have already been consumed
+fn if_flow(lex: &mut Tokens) -> Result<If, ParseStop> {
+    let mut segments = vec![IfSegment {
+        condition: next(lex, false)?,
+        body: body_flow(lex)?,
+    }];
+
+    loop {
+        if lex.peek() != Some(&Token::Else) {
+            return Ok(If {
+                segments,
+                default: None,
+            });
+        }
+
+        lex.next();
+
+        if lex.peek() == Some(&Token::If) {
+            lex.next();
+            segments.push(IfSegment {
+                condition: next(lex, false)?,
+                body: body_flow(lex)?,
+            });
+        } else {
+            return Ok(If {
+                segments,
+                default: Some(IfDefault(body_flow(lex)?)),
+            });
+        }
+    }
+}
+
+/// Flow for `break` grammar, only expecting a value to pass back if the
+/// current body or file doesn't end immediately after
+fn break_flow(lex: &mut Tokens) -> Result<Break, ParseStop> {
+    match lex.peek() {
+        None | Some(Token::Semicolon) | Some(Token::BraceRight) => Ok(Break(None)),
+        Some(_) => Ok(Break(Some(box_next(lex)?))),
+    }
+}
+
+/// Flow for a `{}` enclosed body of expressions, used for the bodies of
+/// definitions and control flow
+fn body_flow(lex: &mut Tokens) -> Result<Vec<Expr>, ParseStop> {
+    ensure(lex, Token::BraceLeft)?;
+    block_flow(lex)
+}
+
+/// Flow for the expressions inside of a [Block] or body, expecting the opening
+/// `{` to have already been consumed
+fn block_flow(lex: &mut Tokens) -> Result<Vec<Expr>, ParseStop> {
+    let mut body = vec![];
+
+    loop {
+        if lex.peek() == Some(&Token::BraceRight) {
+            lex.next();
+            return Ok(body);
+        }
+
+        let expr = statement(lex, false)?;
+        separator(lex, &expr, Some(&Token::BraceRight))?;
+        body.push(expr);
+    }
+}
+
+/// Consumes the `;` seperator after a given `expr`, which may only be left out
+/// if the `end` of the current body or file follows or if `expr` itself ended
+/// with a `}` enclosed body
+fn separator(lex: &mut Tokens, expr: &Expr, end: Option<&Token>) -> Result<(), ParseStop> {
+    match lex.peek() {
+        Some(Token::Semicolon) => {
+            lex.next();
+            Ok(())
+        }
+        next if next == end => Ok(()),
+        _ if ends_in_body(&expr.kind) => Ok(()),
+        Some(Token::Error) | None => Err(unexpected(
+            lex,
+            vec![Token::Semicolon.into(), Token::BraceRight.into()],
+        )),
+        Some(found) => {
+            let found = found.clone().into_owned();
+            let span = lex.peek_span();
+
+            Err(ParseStop::NoSeparator {
+                found,
+                text: lex.source()[span.clone()].to_string(),
+                span,
+                after: after(lex),
+            })
+        }
+    }
+}
+
+/// Checks if an expression kind ends in a `}` enclosed body, or for bodiless
+/// [Class] definitions, is always followed by its own `;`
+fn ends_in_body(kind: &ExprKind) -> bool {
+    matches!(
+        kind,
+        ExprKind::Block(_)
+            | ExprKind::Class(_)
+            | ExprKind::Function(_)
+            | ExprKind::Method(_)
+            | ExprKind::If(_)
+            | ExprKind::While(_)
+            | ExprKind::Loop(_)
+    )
+}
+
+/// Gets id from next [Lexer] token or errors
+fn get_id(lex: &mut Tokens) -> Result<Id, ParseStop> {
+    match lex.peek() {
+        Some(Token::Id(name)) => {
+            let name = name.to_string();
+            lex.next();
+
+            Ok(Id {
+                name,
+                range: lex.span(),
+            })
+        }
+        _ => Err(unexpected(lex, vec![Expected::Id])),
+    }
+}
+
+/// Converts the normalised `path` of the [Token::Path] just consumed by `lex`
+/// into [Id]s, each with their own range
+fn path_ids(path: &str, lex: &Tokens) -> Vec<Id> {
+    let mut start = lex.span().start;
+
+    // names are normalised so their lengths are taken from the source instead
+    path.split("::")
+        .map(|name| name.to_string())
+        .zip(lex.slice().split("::"))
+        .map(|(name, source)| {
+            let range = start..start + source.len();
+            start = range.end + 2; // skip over `::`
+
+            Id { name, range }
+        })
+        .collect()
+}
+
+/// Gets next expression and returns a [Box], used as a shortcut for sequential parsing
+fn box_next(lex: &mut Tokens) -> Result<Box<Expr>, ParseStop> {
+    Ok(Box::new(next(lex, false)?))
+}
+
+/// Gets next single expression and returns a [Box], used for prefix operators
+/// which bind tighter than any binary operation apart from exponents
+fn box_single(lex: &mut Tokens) -> Result<Box<Expr>, ParseStop> {
+    let operand = single(lex, false)?;
+    Ok(Box::new(op_flow(lex, operand, POW_PREC)?))
+}
+
+/// Creates a new lexer which has just consumed the token at `span` of the
+/// given `source`, or nothing if `span` is empty
+fn rewind(source: &str, span: Span) -> Tokens<'_> {
+    let mut lex = Tokens::at(source, span.start);
+
+    if !span.is_empty() {
+        lex.next();
+    }
+
+    lex
+}
+
+/// Ensures next lex token equals inputted `token` value, consuming it if so
+fn ensure(lex: &mut Tokens, token: Token<'static>) -> Result<(), ParseStop> {
+    if lex.peek() == Some(&token) {
+        lex.next();
+        Ok(())
+    } else {
+        Err(unexpected(lex, vec![token.into()]))
+    }
+}
+
+/// Creates an error for the next lex token without consuming it, used when it
+/// isn't one of the `expected` tokens
+fn unexpected(lex: &mut Tokens, expected: Vec<Expected>) -> ParseStop {
+    let span = lex.peek_span();
+    let text = lex.source()[span.clone()].to_string();
+
+    match lex.peek().cloned() {
+        Some(Token::Error) => match lex.peek_error() {
+            Some(error) => ParseStop::InvalidToken { span, error },
+            None => ParseStop::UnknownToken { span, text },
+        },
+        Some(found) => ParseStop::UnexpectedToken {
+            span,
+            found: found.into_owned(),
+            text,
+            expected,
+            after: after(lex),
+        },
+        None => ParseStop::UnexpectedEof {
+            span,
+            expected,
+            after: after(lex),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Range;
+
+    /// Shortcut for lexing `source` from the start in tests
+    fn tokens(source: &str) -> Tokens<'_> {
+        Tokens::at(source, 0)
+    }
+
+    /// Shortcut for creating an undocumented [Expr] in tests
+    fn expr(kind: impl Into<ExprKind>, range: Range<usize>) -> Expr {
+        Expr::from_parse(kind, None, range)
+    }
+
+    /// Shortcut for creating an [Op] expression in tests, spanning from the
+    /// start of `left` to the end of `right`
+    fn op(left: Expr, right: Expr, kind: OpKind) -> Expr {
+        let range = left.range.start..right.range.end;
+
+        expr(
+            Op {
+                left: Box::new(left),
+                right: Box::new(right),
+                kind,
+            },
+            range,
+        )
+    }
+
+    /// Shortcut for creating an [Id] starting at `start` in tests
+    fn name(name: &str, start: usize) -> Id {
+        Id {
+            name: name.to_string(),
+            range: start..start + name.len(),
+        }
+    }
+
+    /// Shortcut for creating a single-id [Path] expression in tests
+    fn id(id: &str, start: usize) -> Expr {
+        expr(Path(vec![name(id, start)]), start..start + id.len())
+    }
+
+    #[test]
+    fn lets() {
+        assert_eq!(
+            next(&mut tokens("let x = 5"), true).unwrap(),
+            expr(
+                Let {
+                    mutable: false,
+                    id: name("x", 4),
+                    expr: Box::new(expr(IntLit(5), 8..9))
+                },
+                0..9
+            )
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(next(&mut tokens("x"), true).unwrap(), id("x", 0));
+        assert_eq!(
+            next(&mut tokens("a::b::c"), true).unwrap(),
+            expr(Path(vec![name("a", 0), name("b", 3), name("c", 6)]), 0..7)
+        );
+        assert_eq!(
+            next(&mut tokens("self.x"), true).unwrap(),
+            expr(
+                Field {
+                    receiver: Box::new(expr(This, 0..4)),
+                    id: name("x", 5)
+                },
+                0..6
+            )
+        );
+    }
+
+    #[test]
+    fn calls() {
+        assert_eq!(
+            next(&mut tokens("foo(1, 2)"), true).unwrap(),
+            expr(
+                FunctionCall {
+                    id: name("foo", 0),
+                    path: Path(vec![]),
+                    args: vec![expr(IntLit(1), 4..5), expr(IntLit(2), 7..8)]
+                },
+                0..9
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("SomeClass::new(3)"), true).unwrap(),
+            expr(
+                FunctionCall {
+                    id: name("new", 11),
+                    path: Path(vec![name("SomeClass", 0)]),
+                    args: vec![expr(IntLit(3), 15..16)]
+                },
+                0..17
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("obj.method(x)"), true).unwrap(),
+            expr(
+                MethodCall {
+                    receiver: Box::new(id("obj", 0)),
+                    id: name("method", 4),
+                    args: vec![id("x", 11)]
+                },
+                0..13
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("empty()"), true).unwrap(),
+            expr(
+                FunctionCall {
+                    id: name("empty", 0),
+                    path: Path(vec![]),
+                    args: vec![]
+                },
+                0..7
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("foo(1 2)"), true).unwrap_err().to_string(),
+            "expected `,` or `)` after `foo(1`, found `2`"
+        );
+    }
+
+    #[test]
+    fn chained_calls() {
+        assert_eq!(
+            next(&mut tokens("SomeClass::new(3).multiply(2)"), true).unwrap(),
+            expr(
+                MethodCall {
+                    receiver: Box::new(expr(
+                        FunctionCall {
+                            id: name("new", 11),
+                            path: Path(vec![name("SomeClass", 0)]),
+                            args: vec![expr(IntLit(3), 15..16)]
+                        },
+                        0..17
+                    )),
+                    id: name("multiply", 18),
+                    args: vec![expr(IntLit(2), 27..28)]
+                },
+                0..29
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("foo().bar"), true).unwrap(),
+            expr(
+                Field {
+                    receiver: Box::new(expr(
+                        FunctionCall {
+                            id: name("foo", 0),
+                            path: Path(vec![]),
+                            args: vec![]
+                        },
+                        0..5
+                    )),
+                    id: name("bar", 6)
+                },
+                0..9
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("(a).b.c = 1"), true).unwrap(),
+            expr(
+                SetLet {
+                    receiver: Some(Box::new(expr(
+                        Field {
+                            receiver: Box::new(expr(Path(vec![name("a", 1)]), 0..3)),
+                            id: name("b", 4)
+                        },
+                        0..5
+                    ))),
+                    id: name("c", 6),
+                    path: Path(vec![]),
+                    expr: Box::new(expr(IntLit(1), 10..11))
+                },
+                0..11
+            )
+        );
+    }
+
+    #[test]
+    fn set_lets() {
+        assert_eq!(
+            next(&mut tokens("self.x = y"), true).unwrap(),
+            expr(
+                SetLet {
+                    receiver: Some(Box::new(expr(This, 0..4))),
+                    id: name("x", 5),
+                    path: Path(vec![]),
+                    expr: Box::new(id("y", 9))
+                },
+                0..10
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("x = 5"), true).unwrap(),
+            expr(
+                SetLet {
+                    receiver: None,
+                    id: name("x", 0),
+                    path: Path(vec![]),
+                    expr: Box::new(expr(IntLit(5), 4..5))
+                },
+                0..5
+            )
+        );
+    }
+
+    #[test]
+    fn op_set_lets() {
+        assert_eq!(
+            next(&mut tokens("x += 1 + 2"), true).unwrap(),
+            expr(
+                OpSetLet {
+                    receiver: None,
+                    id: name("x", 0),
+                    path: Path(vec![]),
+                    kind: OpKind::PlusEq,
+                    expr: Box::new(op(
+                        expr(IntLit(1), 5..6),
+                        expr(IntLit(2), 9..10),
+                        OpKind::Add
+                    ))
+                },
+                0..10
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("self.count -= y"), true).unwrap(),
+            expr(
+                OpSetLet {
+                    receiver: Some(Box::new(expr(This, 0..4))),
+                    id: name("count", 5),
+                    path: Path(vec![]),
+                    kind: OpKind::SubEq,
+                    expr: Box::new(id("y", 14))
+                },
+                0..15
+            )
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("a *= 2; b /= 2;"))
+                .unwrap()
+                .into_iter()
+                .map(|expr| match expr.kind {
+                    ExprKind::OpSetLet(OpSetLet { kind, .. }) => kind,
+                    _ => panic!("expected compound assignment"),
+                })
+                .collect::<Vec<_>>(),
+            vec![OpKind::MulEq, OpKind::DivEq]
+        );
+        assert_eq!(
+            next(&mut tokens("x +="), true).unwrap_err().to_string(),
+            "expected an expression after `x +=`, found end of file"
+        );
+    }
+
+    #[test]
+    fn basic_errs() {
+        assert_eq!(
+            next(&mut tokens("let x + 5"), true),
+            Err(ParseStop::UnexpectedToken {
+                span: 6..7,
+                found: Token::Plus,
+                text: "+".to_string(),
+                expected: vec![Expected::Token(Token::Equals)],
+                after: Some("let x".to_string())
+            })
+        );
+        assert_eq!(
+            next(&mut tokens("let x + 5"), true)
+                .unwrap_err()
+                .to_string(),
+            "expected `=` after `let x`, found `+`"
+        );
+        assert_eq!(
+            next(&mut tokens("#"), true),
+            Err(ParseStop::UnknownToken {
+                span: 0..1,
+                text: "#".to_string()
+            })
+        );
+        assert_eq!(
+            next(&mut tokens(r#"let x = "\q";"#), true),
+            Err(ParseStop::InvalidToken {
+                span: 8..12,
+                error: LexError::InvalidEscape(r"\q".to_string())
+            })
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let big = 1 + 99999999999999999999;")),
+            Err(ParseStop::InvalidToken {
+                span: 14..34,
+                error: LexError::IntTooLarge
+            })
+        );
+        assert_eq!(
+            launch(&mut Token::lexer(r#"print("hi);"#))
+                .unwrap_err()
+                .to_string(),
+            "Unterminated string, expected a closing `\"`"
+        );
+        assert_eq!(
+            next(&mut tokens("let x = -- 5"), true),
+            Err(ParseStop::UnexpectedEof {
+                span: 12..12,
+                expected: vec![Expected::Expr],
+                after: Some("let x =".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn ops() {
+        assert_eq!(
+            next(&mut tokens("1 + 2 * 3"), true).unwrap(),
+            op(
+                expr(IntLit(1), 0..1),
+                op(expr(IntLit(2), 4..5), expr(IntLit(3), 8..9), OpKind::Mul),
+                OpKind::Add
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("1 - 2 - 3"), true).unwrap(),
+            op(
+                op(expr(IntLit(1), 0..1), expr(IntLit(2), 4..5), OpKind::Sub),
+                expr(IntLit(3), 8..9),
+                OpKind::Sub
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("a == b and c"), true).unwrap(),
+            op(
+                op(id("a", 0), id("b", 5), OpKind::EqEq),
+                id("c", 11),
+                OpKind::And
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("a or b and c < d / 2"), true).unwrap(),
+            op(
+                id("a", 0),
+                op(
+                    id("b", 5),
+                    op(
+                        id("c", 11),
+                        op(id("d", 15), expr(IntLit(2), 19..20), OpKind::Div),
+                        OpKind::Less
+                    ),
+                    OpKind::And
+                ),
+                OpKind::Or
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("foo(1 + 2) != 3"), true).unwrap(),
+            op(
+                expr(
+                    FunctionCall {
+                        id: name("foo", 0),
+                        path: Path(vec![]),
+                        args: vec![op(
+                            expr(IntLit(1), 4..5),
+                            expr(IntLit(2), 8..9),
+                            OpKind::Add
+                        )]
+                    },
+                    0..10
+                ),
+                expr(IntLit(3), 14..15),
+                OpKind::NotEq
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("* 2"), true).unwrap_err().to_string(),
+            "Operation `*` was found with no lefthand expression"
+        );
+    }
+
+    #[test]
+    fn int_limits() {
+        assert_eq!(
+            next(&mut tokens("-9223372036854775808"), true).unwrap(),
+            expr(IntLit(i64::MIN), 0..20)
+        );
+        assert_eq!(
+            next(&mut tokens("9223372036854775807"), true).unwrap(),
+            expr(IntLit(i64::MAX), 0..19)
+        );
+        assert_eq!(
+            next(&mut tokens("9223372036854775808"), true),
+            Err(ParseStop::InvalidToken {
+                span: 0..19,
+                error: LexError::IntTooLarge
+            })
+        );
+        assert_eq!(
+            next(&mut tokens("-9223372036854775809"), true),
+            Err(ParseStop::InvalidToken {
+                span: 0..20,
+                error: LexError::IntTooLarge
+            })
+        );
+        assert_eq!(
+            next(&mut tokens("-2 ** 2"), true).unwrap(),
+            expr(
+                Neg(Box::new(op(
+                    expr(IntLit(2), 1..2),
+                    expr(IntLit(2), 6..7),
+                    OpKind::Pow
+                ))),
+                0..7
+            )
+        );
+    }
+
+    #[test]
+    fn documented_ops() {
+        let mut documented = op(expr(IntLit(1), 8..9), expr(IntLit(2), 12..13), OpKind::Add);
+        documented.doc = Some("doc".into());
+
+        assert_eq!(
+            launch(&mut Token::lexer("--- doc\n1 + 2;")).unwrap(),
+            vec![documented]
+        );
+    }
+
+    #[test]
+    fn extended_ops() {
+        assert_eq!(
+            next(&mut tokens("a | b ^ c & d"), true).unwrap(),
+            op(
+                id("a", 0),
+                op(
+                    id("b", 4),
+                    op(id("c", 8), id("d", 12), OpKind::BitAnd),
+                    OpKind::BitXor
+                ),
+                OpKind::BitOr
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("x & 1 << 4 == 0"), true).unwrap(),
+            op(
+                op(
+                    id("x", 0),
+                    op(expr(IntLit(1), 4..5), expr(IntLit(4), 9..10), OpKind::Shl),
+                    OpKind::BitAnd
+                ),
+                expr(IntLit(0), 14..15),
+                OpKind::EqEq
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("a >> 1 + 2 % 3"), true).unwrap(),
+            op(
+                id("a", 0),
+                op(
+                    expr(IntLit(1), 5..6),
+                    op(expr(IntLit(2), 9..10), expr(IntLit(3), 13..14), OpKind::Mod),
+                    OpKind::Add
+                ),
+                OpKind::Shr
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("2 * 3 ** 2 ** 4"), true).unwrap(),
+            op(
+                expr(IntLit(2), 0..1),
+                op(
+                    expr(IntLit(3), 4..5),
+                    op(expr(IntLit(2), 9..10), expr(IntLit(4), 14..15), OpKind::Pow),
+                    OpKind::Pow
+                ),
+                OpKind::Mul
+            )
+        );
+    }
+
+    #[test]
+    fn ifs() {
+        assert_eq!(
+            next(&mut tokens("if a { 1; 2 }"), true).unwrap(),
+            expr(
+                If {
+                    segments: vec![IfSegment {
+                        condition: id("a", 3),
+                        body: vec![expr(IntLit(1), 7..8), expr(IntLit(2), 10..11)]
+                    }],
+                    default: None
+                },
+                0..13
+            )
+        );
+        assert_eq!(
+            next(
+                &mut tokens("if a == 1 { x } else if b { y } else { z }"),
+                true
+            )
+            .unwrap(),
+            expr(
+                If {
+                    segments: vec![
+                        IfSegment {
+                            condition: op(id("a", 3), expr(IntLit(1), 8..9), OpKind::EqEq),
+                            body: vec![id("x", 12)]
+                        },
+                        IfSegment {
+                            condition: id("b", 24),
+                            body: vec![id("y", 28)]
+                        }
+                    ],
+                    default: Some(IfDefault(vec![id("z", 39)]))
+                },
+                0..42
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("let x = if a {} else { 2 }"), true).unwrap(),
+            expr(
+                Let {
+                    mutable: false,
+                    id: name("x", 4),
+                    expr: Box::new(expr(
+                        If {
+                            segments: vec![IfSegment {
+                                condition: id("a", 11),
+                                body: vec![]
+                            }],
+                            default: Some(IfDefault(vec![expr(IntLit(2), 23..24)]))
+                        },
+                        8..26
+                    ))
+                },
+                0..26
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("if a { 1"), true).unwrap_err().to_string(),
+            "expected `;` or `}` after `1`, found end of file"
+        );
+        assert_eq!(
+            next(&mut tokens("if a 1"), true).unwrap_err().to_string(),
+            "expected `{` after `if a`, found `1`"
+        );
+    }
+
+    #[test]
+    fn loops() {
+        assert_eq!(
+            next(&mut tokens("while x < 10 { continue; break }"), true).unwrap(),
+            expr(
+                While {
+                    condition: Box::new(op(id("x", 6), expr(IntLit(10), 10..12), OpKind::Less)),
+                    body: vec![expr(Continue, 15..23), expr(Break(None), 25..30)]
+                },
+                0..32
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("loop { break 1 + 2; }"), true).unwrap(),
+            expr(
+                Loop {
+                    body: vec![expr(
+                        Break(Some(Box::new(op(
+                            expr(IntLit(1), 13..14),
+                            expr(IntLit(2), 17..18),
+                            OpKind::Add
+                        )))),
+                        7..18
+                    )]
+                },
+                0..21
+            )
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("break")).unwrap(),
+            vec![expr(Break(None), 0..5)]
+        );
+        assert_eq!(
+            next(&mut tokens("while x 1"), true)
+                .unwrap_err()
+                .to_string(),
+            "expected `{` after `while x`, found `1`"
+        );
+    }
+
+    #[test]
+    fn funs() {
+        assert_eq!(
+            next(&mut tokens("fun add(a, b) { a + b }"), true).unwrap(),
+            expr(
+                Function {
+                    id: name("add", 4),
+                    args: vec![name("a", 8), name("b", 11)],
+                    body: vec![op(id("a", 16), id("b", 20), OpKind::Add)]
+                },
+                0..23
+            )
+        );
+        assert_eq!(
+            launch(&mut Token::lexer(
+                "--- Creates new [SomeClass] from `x` value\nfun SomeClass::new(x) {\n    self.x = x;\n}\n\n--- Multiplies number on record with `y`\nfun SomeClass.multiply(y) {\n    return self.x * y;\n}"
+            ))
+            .unwrap(),
+            vec![
+                Expr {
+                    kind: ExprKind::Method(Method {
+                        class_id: name("SomeClass", 47),
+                        creation_method: true,
+                        id: name("new", 58),
+                        args: vec![name("x", 62)],
+                        body: vec![expr(
+                            SetLet {
+                                receiver: Some(Box::new(expr(This, 71..75))),
+                                id: name("x", 76),
+                                path: Path(vec![]),
+                                expr: Box::new(id("x", 80))
+                            },
+                            71..81
+                        )]
+                    }),
+                    doc: Some("Creates new [SomeClass] from `x` value".into()),
+                    range: 43..84
+                },
+                Expr {
+                    kind: ExprKind::Method(Method {
+                        class_id: name("SomeClass", 131),
+                        creation_method: false,
+                        id: name("multiply", 141),
+                        args: vec![name("y", 150)],
+                        body: vec![expr(
+                            Return(Box::new(op(
+                                expr(
+                                    Field { receiver: Box::new(expr(This, 166..170)), id: name("x", 171) },
+                                    166..172
+                                ),
+                                id("y", 175),
+                                OpKind::Mul
+                            ))),
+                            159..176
+                        )]
+                    }),
+                    doc: Some("Multiplies number on record with `y`".into()),
+                    range: 127..179
+                }
+            ]
+        );
+        assert_eq!(
+            next(&mut tokens("fun empty() {}"), true).unwrap(),
+            expr(
+                Function {
+                    id: name("empty", 4),
+                    args: vec![],
+                    body: vec![]
+                },
+                0..14
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("fun a::b::c() {}"), true)
+                .unwrap_err()
+                .to_string(),
+            "expected an identifier after `fun`, found `a::b::c`"
+        );
+        assert_eq!(
+            next(&mut tokens("fun add(a b) {}"), true)
+                .unwrap_err()
+                .to_string(),
+            "expected `,` or `)` after `fun add(a`, found `b`"
+        );
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(
+            next(&mut tokens("class Point;"), true).unwrap(),
+            expr(
+                Class {
+                    id: name("Point", 6),
+                    fields: vec![]
+                },
+                0..11
+            )
+        );
+        assert_eq!(
+            next(
+                &mut tokens("class Point {\n    --- Horizontal\n    x = 0,\n    y,\n}"),
+                true
+            )
+            .unwrap(),
+            expr(
+                Class {
+                    id: name("Point", 6),
+                    fields: vec![
+                        ClassField {
+                            id: name("x", 37),
+                            default: Some(expr(IntLit(0), 41..42)),
+                            doc: Some("Horizontal".into())
+                        },
+                        ClassField {
+                            id: name("y", 48),
+                            default: None,
+                            doc: None
+                        }
+                    ]
+                },
+                0..52
+            )
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("class A;\n-1;")).unwrap(),
+            vec![
+                expr(
+                    Class {
+                        id: name("A", 6),
+                        fields: vec![]
+                    },
+                    0..7
+                ),
+                expr(IntLit(-1), 9..11)
+            ]
+        );
+        assert_eq!(
+            next(&mut tokens("class Point { x y }"), true)
+                .unwrap_err()
+                .to_string(),
+            "expected `=`, `,` or `}` after `x`, found `y`"
+        );
+        assert_eq!(
+            next(&mut tokens("class Point"), true)
+                .unwrap_err()
+                .to_string(),
+            "expected `;` or `{` after `class Point`, found end of file"
+        );
+    }
+
+    #[test]
+    fn multiline_and_raw_strs() {
+        assert_eq!(
+            launch(&mut Token::lexer(
+                "let json = \"\"\"\n    {\"a\": 1}\n    \"\"\";\nlet path = r\"C:\\dir\";"
+            ))
+            .unwrap(),
+            vec![
+                expr(
+                    Let {
+                        mutable: false,
+                        id: name("json", 4),
+                        expr: Box::new(expr(StrLit("{\"a\": 1}".to_string()), 11..35))
+                    },
+                    0..35
+                ),
+                expr(
+                    Let {
+                        mutable: false,
+                        id: name("path", 41),
+                        expr: Box::new(expr(StrLit("C:\\dir".to_string()), 48..57))
+                    },
+                    37..57
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn interp_strs() {
+        assert_eq!(
+            next(&mut tokens(r#""hello {name}, you are {age + 1}""#), true).unwrap(),
+            expr(
+                InterpStr(vec![
+                    InterpPart::Lit("hello ".to_string()),
+                    InterpPart::Expr(Box::new(id("name", 8))),
+                    InterpPart::Lit(", you are ".to_string()),
+                    InterpPart::Expr(Box::new(op(
+                        id("age", 24),
+                        expr(IntLit(1), 30..31),
+                        OpKind::Add
+                    )))
+                ]),
+                0..33
+            )
+        );
+        assert_eq!(
+            next(&mut tokens(r#""hi {greet("bob")}""#), true).unwrap(),
+            expr(
+                InterpStr(vec![
+                    InterpPart::Lit("hi ".to_string()),
+                    InterpPart::Expr(Box::new(expr(
+                        FunctionCall {
+                            id: name("greet", 5),
+                            path: Path(vec![]),
+                            args: vec![expr(StrLit("bob".to_string()), 11..16)]
+                        },
+                        5..17
+                    )))
+                ]),
+                0..19
+            )
+        );
+        assert_eq!(
+            next(&mut tokens(r#"x + "{ "\}" }" + "{ "}" }""#), true),
+            Err(ParseStop::InvalidToken {
+                span: 20..23,
+                error: LexError::UnopenedInterpolation
+            })
+        );
+        assert_eq!(
+            next(&mut tokens(r#""{x}" + "\{x\}""#), true).unwrap(),
+            op(
+                expr(
+                    InterpStr(vec![InterpPart::Expr(Box::new(id("x", 2)))]),
+                    0..5
+                ),
+                expr(StrLit("{x}".to_string()), 8..15),
+                OpKind::Add
+            )
+        );
+        assert_eq!(
+            next(&mut tokens(r#""{a b}""#), true)
+                .unwrap_err()
+                .to_string(),
+            "expected `}` after `\"{a`, found `b`"
+        );
+        assert_eq!(
+            next(&mut tokens(r#""sum: {1 +}""#), true)
+                .unwrap_err()
+                .to_string(),
+            "expected an expression after `\"sum: {1 +`, found `}`"
+        );
+    }
+
+    #[test]
+    fn unicode_ids() {
+        assert_eq!(next(&mut tokens("größe"), true).unwrap(), id("größe", 0));
+        assert_eq!(
+            next(&mut tokens("cafe\u{301}::x = 1"), true).unwrap(),
+            expr(
+                SetLet {
+                    receiver: None,
+                    id: Id {
+                        name: "x".to_string(),
+                        range: 8..9
+                    },
+                    path: Path(vec![Id {
+                        name: "café".to_string(),
+                        range: 0..6
+                    }]),
+                    expr: Box::new(expr(IntLit(1), 12..13))
+                },
+                0..13
+            )
+        );
+    }
+
+    #[test]
+    fn nones_and_selfs() {
+        assert_eq!(
+            next(&mut tokens("none"), true).unwrap(),
+            expr(NoneLit, 0..4)
+        );
+        assert_eq!(
+            next(&mut tokens("return self"), true).unwrap(),
+            expr(Return(Box::new(expr(This, 7..11))), 0..11)
+        );
+        assert_eq!(
+            next(&mut tokens("self.x == none"), true).unwrap(),
+            op(
+                expr(
+                    Field {
+                        receiver: Box::new(expr(This, 0..4)),
+                        id: name("x", 5)
+                    },
+                    0..6
+                ),
+                expr(NoneLit, 10..14),
+                OpKind::EqEq
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("self.add(none)"), true).unwrap(),
+            expr(
+                MethodCall {
+                    receiver: Box::new(expr(This, 0..4)),
+                    id: name("add", 5),
+                    args: vec![expr(NoneLit, 9..13)]
+                },
+                0..14
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("this.x"), true),
+            Err(ParseStop::InvalidToken {
+                span: 0..4,
+                error: LexError::ReservedThis
+            })
+        );
+    }
+
+    #[test]
+    fn readme_example() {
+        let parsed = launch(&mut Token::lexer(
+            "--- Small test class, helping to describe some features of Jingo\nclass SomeClass;\n\n--- Creates new [SomeClass] from `x` value\nfun SomeClass::new(x) {\n    self.x = x;\n}\n\n--- Multiplies number on record with `y`\nfun SomeClass.multiply(y) {\n    return self.x * y;\n}\n\nif 1 + 2 == 3 {\n    let my_class = SomeClass::new(3);\n    my_class.multiply(2); -- will be 3 * 2 = 6\n}\n",
+        ))
+        .unwrap();
+
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(
+            parsed[0],
+            Expr {
+                kind: ExprKind::Class(Class {
+                    id: name("SomeClass", 71),
+                    fields: vec![]
+                }),
+                doc: Some("Small test class, helping to describe some features of Jingo".into()),
+                range: 65..80
+            }
+        );
+    }
+
+    #[test]
+    fn prefixes() {
+        assert_eq!(
+            next(&mut tokens("-2 ** 2 * ~x"), true).unwrap(),
+            op(
+                expr(
+                    Neg(Box::new(op(
+                        expr(IntLit(2), 1..2),
+                        expr(IntLit(2), 6..7),
+                        OpKind::Pow
+                    ))),
+                    0..7
+                ),
+                expr(BitNot(Box::new(id("x", 11))), 10..12),
+                OpKind::Mul
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("!a == b"), true).unwrap(),
+            op(
+                expr(Not(Box::new(id("a", 1))), 0..2),
+                id("b", 6),
+                OpKind::EqEq
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("-5 * -x"), true).unwrap(),
+            op(
+                expr(IntLit(-5), 0..2),
+                expr(Neg(Box::new(id("x", 6))), 5..7),
+                OpKind::Mul
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("(1 + 2) * 3"), true).unwrap(),
+            op(
+                expr(
+                    Op {
+                        left: Box::new(expr(IntLit(1), 1..2)),
+                        right: Box::new(expr(IntLit(2), 5..6)),
+                        kind: OpKind::Add
+                    },
+                    0..7
+                ),
+                expr(IntLit(3), 10..11),
+                OpKind::Mul
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("!(a and b)"), true).unwrap(),
+            expr(
+                Not(Box::new(expr(
+                    Op {
+                        left: Box::new(id("a", 2)),
+                        right: Box::new(id("b", 8)),
+                        kind: OpKind::And
+                    },
+                    1..10
+                ))),
+                0..10
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("1 - -1"), true).unwrap(),
+            op(expr(IntLit(1), 0..1), expr(IntLit(-1), 4..6), OpKind::Sub)
+        );
+        assert_eq!(
+            next(&mut tokens("-1.5 + -x.y"), true).unwrap(),
+            op(
+                expr(FloatLit(-1.5), 0..4),
+                expr(
+                    Neg(Box::new(expr(
+                        Field {
+                            receiver: Box::new(id("x", 8)),
+                            id: name("y", 10)
+                        },
+                        8..11
+                    ))),
+                    7..11
+                ),
+                OpKind::Add
+            )
+        );
+        assert_eq!(
+            next(&mut tokens("(1 + 2"), true).unwrap_err().to_string(),
+            "expected `)` after `(1 + 2`, found end of file"
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            next(&mut tokens("{ a; b; c }"), true).unwrap(),
+            expr(Block(vec![id("a", 2), id("b", 5), id("c", 8)]), 0..11)
+        );
+        assert_eq!(
+            next(&mut tokens("{}"), true).unwrap(),
+            expr(Block(vec![]), 0..2)
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let x = { 1 };\nif x {} { x }")).unwrap(),
+            vec![
+                expr(
+                    Let {
+                        mutable: false,
+                        id: name("x", 4),
+                        expr: Box::new(expr(Block(vec![expr(IntLit(1), 10..11)]), 8..13))
+                    },
+                    0..13
+                ),
+                expr(
+                    If {
+                        segments: vec![IfSegment {
+                            condition: id("x", 18),
+                            body: vec![]
+                        }],
+                        default: None
+                    },
+                    15..22
+                ),
+                expr(Block(vec![id("x", 25)]), 23..28)
+            ]
+        );
+    }
+
+    #[test]
+    fn separators() {
+        assert_eq!(
+            launch(&mut Token::lexer("1; 2;")).unwrap(),
+            vec![expr(IntLit(1), 0..1), expr(IntLit(2), 3..4)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 2")).unwrap_err().to_string(),
+            "expected `;` after `1`, found `2`"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("fun a() { 1 2 }"))
+                .unwrap_err()
+                .to_string(),
+            "expected `;` after `1`, found `2`"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let a = 1 let b = 2"))
+                .unwrap_err()
+                .to_string(),
+            "expected `;` after `let a = 1`, found `let`"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 1_000"))
+                .unwrap_err()
+                .to_string(),
+            "expected `;` after `1`, found `1_000`"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 0xFF")),
+            Err(ParseStop::NoSeparator {
+                span: 2..6,
+                found: Token::Int(255),
+                text: "0xFF".to_string(),
+                after: Some("1".to_string())
+            })
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let x 0xFF"))
+                .unwrap_err()
+                .to_string(),
+            "expected `=` after `let x`, found `0xFF`"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("x r\"a\\b\""))
+                .unwrap_err()
+                .to_string(),
+            "expected `;` after `x`, found `r\"a\\b\"`"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("fun f() {}\n-x;")).unwrap(),
+            vec![
+                expr(
+                    Function {
+                        id: name("f", 4),
+                        args: vec![],
+                        body: vec![]
+                    },
+                    0..10
+                ),
+                expr(Neg(Box::new(id("x", 12))), 11..13)
+            ]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("if a { b }\n-1;")).unwrap(),
+            vec![
+                expr(
+                    If {
+                        segments: vec![IfSegment {
+                            condition: id("a", 3),
+                            body: vec![id("b", 7)]
+                        }],
+                        default: None
+                    },
+                    0..10
+                ),
+                expr(IntLit(-1), 11..13)
+            ]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("if a {1} else {2} + 1"))
+                .unwrap_err()
+                .to_string(),
+            "expected `;` after `if a {1} else {2}`, found `+`, wrap it in `( )` to use it as an operand"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("fun f() {\n    1\n} * 2")),
+            Err(ParseStop::BodyOperand {
+                span: 18..19,
+                found: Token::Star,
+                text: "*".to_string(),
+                after: Some("fun f() { …".to_string())
+            })
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("{ fun f() {}\n-x }")).unwrap(),
+            vec![expr(
+                Block(vec![
+                    expr(
+                        Function {
+                            id: name("f", 6),
+                            args: vec![],
+                            body: vec![]
+                        },
+                        2..12
+                    ),
+                    expr(Neg(Box::new(id("x", 14))), 13..15)
+                ]),
+                0..17
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1;;")).unwrap_err().to_string(),
+            "expected an expression after `1;`, found `;`"
+        );
+    }
+
+    #[test]
+    fn recovery() {
+        let (output, errors) = launch_recover(&mut Token::lexer(
+            "let a = +;\nfun b() {\n    if a { 1 2 } else { 3 }\n}\nlet c = 3 let d = 4;\nfun e(1) {}\n5",
+        ));
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| err.span().unwrap())
+                .collect::<Vec<_>>(),
+            vec![8..9, 34..35, 61..64, 78..79]
+        );
+        assert_eq!(
+            output,
+            vec![
+                expr(
+                    Let {
+                        mutable: false,
+                        id: name("c", 55),
+                        expr: Box::new(expr(IntLit(3), 59..60))
+                    },
+                    51..60
+                ),
+                expr(
+                    Let {
+                        mutable: false,
+                        id: name("d", 65),
+                        expr: Box::new(expr(IntLit(4), 69..70))
+                    },
+                    61..70
+                ),
+                expr(IntLit(5), 84..85)
+            ]
+        );
+        assert_eq!(
+            launch_recover(&mut Token::lexer("let x = { a b };\nclass A { x y };\n1")),
+            (
+                vec![expr(IntLit(1), 34..35)],
+                vec![
+                    ParseStop::NoSeparator {
+                        span: 12..13,
+                        found: Token::Id("b".into()),
+                        text: "b".to_string(),
+                        after: Some("a".to_string())
+                    },
+                    ParseStop::UnexpectedToken {
+                        span: 29..30,
+                        found: Token::Id("y".into()),
+                        text: "y".to_string(),
+                        expected: vec![
+                            Expected::Token(Token::Equals),
+                            Expected::Token(Token::Comma),
+                            Expected::Token(Token::BraceRight)
+                        ],
+                        after: Some("x".to_string())
+                    }
+                ]
+            )
+        );
+        assert_eq!(
+            launch_recover(&mut Token::lexer("1 + }; 2")),
+            (
+                vec![expr(IntLit(2), 7..8)],
+                vec![ParseStop::UnexpectedToken {
+                    span: 4..5,
+                    found: Token::BraceRight,
+                    text: "}".to_string(),
+                    expected: vec![Expected::Expr],
+                    after: Some("1 +".to_string())
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_launch() {
+        assert_eq!(
+            launch(&mut Token::lexer("5 + 3")).unwrap(),
+            vec![op(
+                expr(IntLit(5), 0..1),
+                expr(IntLit(3), 4..5),
+                OpKind::Add
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("!5")).unwrap(),
+            vec![expr(Not(Box::new(expr(IntLit(5), 1..2))), 0..2)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("+ 5")),
+            Err(ParseStop::NoLeftExpr {
+                span: 0..1,
+                found: Token::Plus,
+                text: "+".to_string()
+            })
+        );
         assert_eq!(
             launch(&mut Token::lexer("5 +")),
-            Err(ParseStop::UnexpectedEof)
+            Err(ParseStop::UnexpectedEof {
+                span: 3..3,
+                expected: vec![Expected::Expr],
+                after: Some("5 +".to_string())
+            })
         );
         assert_eq!(
             launch(&mut Token::lexer("5 + 5 + 5 +")),
-            Err(ParseStop::UnexpectedEof)
+            Err(ParseStop::UnexpectedEof {
+                span: 11..11,
+                expected: vec![Expected::Expr],
+                after: Some("5 + 5 + 5 +".to_string())
+            })
         );
     }
 }